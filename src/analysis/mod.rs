@@ -2,10 +2,16 @@ pub mod rl_analysis;
 pub mod utils;
 
 use std::cell::Cell;
+use std::collections::{BTreeMap, BTreeSet};
 
 use crate::CliArgs;
-use rl_analysis::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
-use rl_analysis::RLAnalysis;
+use rl_analysis::rl_graph::{RLEdge, RLGraph, RLGraphNode, RLIndex, RLNode};
+use rl_analysis::{
+    EntryPointKind, Granularity, RLAnalysis, RLArgType, RLBorrowSuggester, RLCentrality,
+    RLCondenser, RLCopySuggester, RLDiff, RLDominatorTreeNode, RLLayeringChecker, RLLayeringRules,
+    RLOwnershipCost, RLOwnershipTracer, RLPathFinder, RLReceiverReporter, RLRuleChecker, RLRules,
+    RLScc,
+};
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::mir;
 use rustc_middle::ty;
@@ -13,6 +19,199 @@ use serde::de::DeserializeOwned;
 use serde::Serialize;
 use utils::{TextMod, RL_SERDE_FOLDER};
 
+/// The number of call sites printed for each usage of a type with `--by-type`.
+const BY_TYPE_TOP_CALL_SITES: usize = 3;
+
+/// Print the entry points of the graph and the local functions which are not reachable
+/// from any of them, e.g., for the crate `temp` or for the workspace.
+pub fn print_reachability<G>(rl_graph: &G, scope: &str, include_public: bool)
+where
+    G: RLGraph<Node = RLNode, Index = RLIndex>,
+{
+    println!("Entry points of {}:", scope);
+    for (entry_point, kind) in rl_graph.entry_points(include_public) {
+        println!(
+            "{} ({:?})",
+            rl_graph.rl_node(entry_point).def_id_str(),
+            kind
+        );
+    }
+    println!("Unreachable functions of {}:", scope);
+    for function in rl_graph.unreachable(include_public) {
+        println!("{}", rl_graph.rl_node(function).def_id_str());
+    }
+}
+
+/// Print the graph condensed at the `granularity` (see `RLCondenser`), as a dot graph and/or
+/// serialized.
+pub fn print_condensed_rl_graph<G>(
+    rl_graph: &G,
+    granularity: Granularity,
+    print_rl_graph: bool,
+    print_serialized_rl_graph: bool,
+) where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    log::debug!(
+        "Condensing the RustyLinks graph at the {:?} granularity",
+        granularity
+    );
+    let condenser = RLCondenser::new(granularity);
+    let condensed_rl_graph = rl_graph.condense(&|node| condenser.group_of(node));
+
+    if print_rl_graph {
+        log::debug!("Printing the condensed RustyLinks graph");
+        println!("{}", condensed_rl_graph.as_dot_str());
+    }
+
+    if print_serialized_rl_graph {
+        log::debug!("Printing the serialized condensed RustyLinks graph");
+        let serialized = serde_json::to_string(&condensed_rl_graph).unwrap();
+        println!("{}", serialized);
+    }
+}
+
+/// Print the ownership chain of each value created in the graph (see `RLOwnershipTracer`),
+/// e.g., for the crate `temp` or for the workspace.
+pub fn print_ownership_chains<G>(rl_graph: &G, scope: &str)
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    println!("Ownership chains of {}:", scope);
+    for chain in RLOwnershipTracer::new(rl_graph).chains() {
+        println!("{}", chain);
+    }
+}
+
+/// Print the `k` cheapest (or most expensive) call paths between the functions `from` and `to`
+/// of the graph (see `RLNode::def_path`), with the arguments of each call.
+/// It fails if `from` or `to` has no call in the graph.
+pub fn print_paths<G>(
+    rl_graph: &G,
+    from: &str,
+    to: &str,
+    k: usize,
+    most_expensive: bool,
+) -> Result<(), String>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    // A function without calls cannot be part of a path, so only the nodes with an edge
+    // are searched.
+    let find_node = |def_path: &str| {
+        rl_graph
+            .rl_edges()
+            .into_iter()
+            .flat_map(|(source, target, _)| [source, target])
+            .find(|node| rl_graph.rl_node(*node).def_path() == Some(def_path))
+            .ok_or_else(|| format!("The function `{}` has no call in the graph", def_path))
+    };
+    let def_path = |node: RLIndex| {
+        let node = rl_graph.rl_node(node);
+        node.def_path()
+            .map_or_else(|| node.def_id_str(), str::to_string)
+    };
+    let (source, target) = (find_node(from)?, find_node(to)?);
+
+    println!(
+        "{} paths from `{}` to `{}`:",
+        if most_expensive {
+            "Most expensive"
+        } else {
+            "Cheapest"
+        },
+        from,
+        to
+    );
+    let paths = RLPathFinder::new(rl_graph).paths(source, target, k, most_expensive);
+    for (i, path) in paths.into_iter().enumerate() {
+        println!("Path {} (ownership score {}):", i + 1, path.cost());
+        for call in path.calls() {
+            let edge = call.edge();
+            let args = edge
+                .arg_weights()
+                .iter()
+                .enumerate()
+                .map(|(i, arg_weight)| match edge.arg_tys().get(i) {
+                    Some(arg_ty) => {
+                        format!("{} {:?} {:?}", arg_ty.ty(), arg_ty.usage(), arg_weight)
+                    }
+                    None => format!("{:?}", arg_weight),
+                })
+                .collect::<Vec<_>>();
+            println!(
+                "    {} -> {} ({}): ownership score {}, {:?} [{}]",
+                def_path(call.caller()),
+                def_path(call.callee()),
+                edge.span().unwrap_or("unknown span"),
+                edge.ownership_score(),
+                edge.call_kind(),
+                args.join(", ")
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Print the functions changed since the revision `since` (see `RLDiff`) and the functions
+/// which transitively call them, grouped by crate, with the tests among them.
+///
+/// The functions are printed by path, since a library compiled also as a test harness
+/// (with `--tests`) has two nodes for each function.
+/// It fails if the diff cannot be computed (see `RLDiff::since`).
+pub fn print_impact<G>(rl_graph: &G, since: &str) -> Result<(), String>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    let diff = RLDiff::since(since)?;
+    let def_path = |node: &RLNode| {
+        node.def_path()
+            .map_or_else(|| node.def_id_str(), str::to_string)
+    };
+
+    let mut changed = Vec::new();
+    let mut changed_paths = BTreeSet::new();
+    for (index, node) in rl_graph.rl_nodes() {
+        if let Some(def_span) = node.def_span().filter(|def_span| diff.touches(def_span)) {
+            changed.push(index);
+            changed_paths.insert(format!("{} ({})", def_path(node), def_span));
+        }
+    }
+    println!("Changed functions since `{}`:", since);
+    for changed_path in changed_paths {
+        println!("{}", changed_path);
+    }
+
+    let mut by_crate: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    let mut tests = BTreeSet::new();
+    for caller in rl_graph.callers(&changed) {
+        let caller = rl_graph.rl_node(caller);
+        // The statically unknown call sites are not functions which can be affected.
+        let (Some(caller_path), Some(krate)) = (caller.def_path(), caller.crate_name()) else {
+            continue;
+        };
+        if caller.entry_point(false) == Some(EntryPointKind::Test) {
+            tests.insert(caller_path.to_string());
+        }
+        by_crate
+            .entry(krate.to_string())
+            .or_default()
+            .insert(caller_path.to_string());
+    }
+    println!("Affected functions since `{}`:", since);
+    for (krate, callers) in by_crate {
+        println!("Crate `{}`:", krate);
+        for caller in callers {
+            println!("    {}", caller);
+        }
+    }
+    println!("Affected tests since `{}`:", since);
+    for test in tests {
+        println!("{}", test);
+    }
+    Ok(())
+}
+
 pub struct Analyzer<'tcx, G>
 where
     G: RLGraph + Default + Clone + Serialize,
@@ -57,14 +256,29 @@ where
     fn post_process_cli_args(&self) {
         log::debug!("Post-processing CLI arguments");
 
-        if !self.cli_args.print_rl_graph && !self.cli_args.print_serialized_rl_graph {
+        if !self.cli_args.print_rl_graph
+            && !self.cli_args.print_serialized_rl_graph
+            && !self.cli_args.print_ownership_chains
+            && !self.cli_args.suggest_borrows
+            && !self.cli_args.print_receivers
+            && !self.cli_args.print_unsafe_surface
+            && !self.cli_args.by_type
+            && !self.cli_args.suggest_copy
+            && !self.cli_args.print_sccs
+            && self.cli_args.hotspots.is_none()
+            && self.cli_args.transitive_cost.is_none()
+            && !self.cli_args.print_unreachable
+            && !self.cli_args.print_dominators
+            && self.cli_args.layering_rules.is_none()
+            && self.cli_args.rules.is_none()
+        {
             log::debug!("No post-processing needed");
             return;
         }
 
         // let rl_graph: G =
         //     self.deserialize_rl_graph_from_file(&self.tcx.crate_name(LOCAL_CRATE).to_string());
-        let rl_graph: G = match self.rl_graph.take() {
+        let mut rl_graph: G = match self.rl_graph.take() {
             Some(rl_graph) => rl_graph,
             None => RLAnalysis::deserialized_rl_graph_from_file(
                 format!(
@@ -76,15 +290,263 @@ where
             ),
         };
 
-        if self.cli_args.print_rl_graph {
-            log::debug!("Printing the RustyLinks graph");
-            println!("{}", rl_graph.as_dot_str());
+        // The costs are computed before printing the graph, which shows them on its nodes.
+        let ownership_costs = match self.cli_args.transitive_cost {
+            Some(_) => {
+                let ownership_costs =
+                    RLOwnershipCost::collect(&rl_graph, self.cli_args.cost_damping);
+                for (node, ownership_cost) in &ownership_costs {
+                    rl_graph
+                        .rl_node_mut(*node)
+                        .set_ownership_cost(*ownership_cost);
+                }
+                ownership_costs
+            }
+            None => Vec::new(),
+        };
+
+        if Granularity::from(self.cli_args.granularity) == Granularity::Fn {
+            if self.cli_args.print_rl_graph {
+                log::debug!("Printing the RustyLinks graph");
+                println!("{}", rl_graph.as_dot_str());
+            }
+
+            if self.cli_args.print_serialized_rl_graph {
+                log::debug!("Printing the serialized RustyLinks graph");
+                let serialized = serde_json::to_string(&rl_graph).unwrap();
+                println!("{}", serialized);
+            }
+        } else if self.cli_args.print_rl_graph || self.cli_args.print_serialized_rl_graph {
+            print_condensed_rl_graph(
+                &rl_graph,
+                self.cli_args.granularity.into(),
+                self.cli_args.print_rl_graph,
+                self.cli_args.print_serialized_rl_graph,
+            );
+        }
+
+        if self.cli_args.print_unsafe_surface {
+            log::debug!("Printing the unsafe surface");
+            println!(
+                "Unsafe surface of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for (public_fn, reached) in rl_graph.unsafe_surface() {
+                println!("{}", rl_graph.rl_node(public_fn).def_id_str());
+                for unsafe_fn in reached {
+                    let node = rl_graph.rl_node(unsafe_fn);
+                    if node.is_unsafe_fn() {
+                        println!("    -> {} (unsafe fn)", node.def_id_str());
+                    } else {
+                        println!("    -> {} (unsafe block)", node.def_id_str());
+                    }
+                }
+            }
+        }
+
+        if self.cli_args.print_sccs {
+            log::debug!("Printing the strongly connected components");
+            println!(
+                "Strongly connected components of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for scc in RLScc::collect(&rl_graph) {
+                println!("SCC with {} functions:", scc.functions().len());
+                for function in scc.functions() {
+                    println!("    {}", rl_graph.rl_node(*function).def_id_str());
+                }
+                for (caller, callee) in scc.calls() {
+                    println!(
+                        "    {} -> {}",
+                        rl_graph.rl_node(*caller).def_id_str(),
+                        rl_graph.rl_node(*callee).def_id_str()
+                    );
+                }
+            }
+        }
+
+        if let Some(hotspots) = self.cli_args.hotspots {
+            log::debug!("Printing the hotspots");
+            println!(
+                "Top {} hotspots of the crate `{}`:",
+                hotspots,
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for (node, centrality) in RLCentrality::collect(&rl_graph).into_iter().take(hotspots) {
+                let node = rl_graph.rl_node(node);
+                println!(
+                    "{} (crate `{}`): {}",
+                    node.def_id_str(),
+                    self.tcx.crate_name(node.def_id().krate),
+                    centrality
+                );
+            }
+        }
+
+        if let Some(transitive_cost) = self.cli_args.transitive_cost {
+            log::debug!("Printing the transitive ownership costs");
+            println!(
+                "Top {} transitive ownership costs of the crate `{}`:",
+                transitive_cost,
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for (node, ownership_cost) in ownership_costs.into_iter().take(transitive_cost) {
+                println!(
+                    "{}: {}",
+                    rl_graph.rl_node(node).def_id_str(),
+                    ownership_cost
+                );
+            }
+        }
+
+        if self.cli_args.print_ownership_chains {
+            log::debug!("Printing the ownership chains");
+            print_ownership_chains(
+                &rl_graph,
+                &format!("the crate `{}`", self.tcx.crate_name(LOCAL_CRATE)),
+            );
+        }
+
+        if self.cli_args.suggest_borrows {
+            log::debug!("Printing the borrow suggestions");
+            println!(
+                "Parameters which can be borrowed in the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for suggestion in RLBorrowSuggester::new(self.tcx, &rl_graph).suggestions() {
+                println!("{}", suggestion);
+            }
+        }
+
+        if self.cli_args.print_receivers {
+            log::debug!("Printing the method receivers");
+            println!(
+                "Method receivers of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for report in RLReceiverReporter::new(self.tcx, &rl_graph).reports() {
+                println!("{}", report);
+            }
+        }
+
+        if self.cli_args.print_unreachable {
+            log::debug!("Printing the unreachable functions");
+            // The public functions can be called by the dependent crates.
+            print_reachability(
+                &rl_graph,
+                &format!("the crate `{}`", self.tcx.crate_name(LOCAL_CRATE)),
+                true,
+            );
+        }
+
+        if self.cli_args.print_dominators {
+            log::debug!("Printing the dominator trees");
+            println!(
+                "Dominator trees of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for (entry_point, kind) in rl_graph.entry_points(true) {
+                println!(
+                    "Entry point {} ({:?}):",
+                    rl_graph.rl_node(entry_point).def_id_str(),
+                    kind
+                );
+                for tree_node in RLDominatorTreeNode::collect(&rl_graph, entry_point) {
+                    let dominated = match tree_node.dominated() {
+                        0 => "".to_string(),
+                        dominated => format!(" (dominates {})", dominated),
+                    };
+                    println!(
+                        "{}{}{}",
+                        "    ".repeat(tree_node.depth() + 1),
+                        rl_graph.rl_node(tree_node.node()).def_id_str(),
+                        dominated
+                    );
+                }
+            }
+        }
+
+        if let Some(layering_rules) = &self.cli_args.layering_rules {
+            log::debug!("Checking the layering rules");
+            let rules = RLLayeringRules::from_file(layering_rules)
+                .unwrap_or_else(|error| self.tcx.dcx().fatal(error));
+            let checker = RLLayeringChecker::new(rules);
+            println!(
+                "Layering violations of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for violation in checker.violations(&rl_graph) {
+                println!("{}", violation);
+            }
+            println!(
+                "Module cycles of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for cycle in checker.module_cycles(&rl_graph) {
+                println!("{}", cycle.join(" <-> "));
+            }
+        }
+
+        if let Some(rules) = &self.cli_args.rules {
+            log::debug!("Checking the user-defined rules");
+            let rules =
+                RLRules::from_file(rules).unwrap_or_else(|error| self.tcx.dcx().fatal(error));
+            let checker = RLRuleChecker::new(rules);
+            println!(
+                "Rule findings of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for finding in checker.findings(&rl_graph) {
+                println!("{}", finding);
+            }
+        }
+
+        if self.cli_args.by_type {
+            log::debug!("Printing the argument types");
+            println!(
+                "Argument types of the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            for arg_type in RLArgType::collect(&rl_graph) {
+                let counts = arg_type
+                    .usages()
+                    .iter()
+                    .map(|usage| format!("{} {:?}", usage.args(), usage.usage()))
+                    .collect::<Vec<_>>();
+                println!("`{}`: {}", arg_type.ty(), counts.join(", "));
+                for usage in arg_type.usages() {
+                    let call_sites = usage
+                        .call_sites()
+                        .iter()
+                        .take(BY_TYPE_TOP_CALL_SITES)
+                        .map(|call_site| {
+                            format!(
+                                "{} -> {} ({})",
+                                rl_graph.rl_node(call_site.caller()).def_id_str(),
+                                rl_graph.rl_node(call_site.callee()).def_id_str(),
+                                call_site.args()
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    println!("    {:?}: {}", usage.usage(), call_sites.join(", "));
+                }
+            }
         }
 
-        if self.cli_args.print_serialized_rl_graph {
-            log::debug!("Printing the serialized RustyLinks graph");
-            let serialized = serde_json::to_string(&rl_graph).unwrap();
-            println!("{}", serialized);
+        if self.cli_args.suggest_copy {
+            log::debug!("Printing the `Copy` suggestions");
+            println!(
+                "Types which could derive `Copy` in the crate `{}`:",
+                self.tcx.crate_name(LOCAL_CRATE)
+            );
+            let copy_suggester = RLCopySuggester::new(
+                self.tcx,
+                self.cli_args.copy_max_size,
+                self.cli_args.copy_min_clones,
+            );
+            for suggestion in copy_suggester.suggestions(&rl_graph) {
+                println!("{}", suggestion);
+            }
         }
     }
 
@@ -1,17 +1,57 @@
 mod rl_args_resolver;
+mod rl_borrow_suggester;
+mod rl_borrowck;
+mod rl_by_type;
 mod rl_call_resolver;
+mod rl_calls;
+mod rl_condensed;
 mod rl_context;
+mod rl_control_flow;
+mod rl_copy_suggester;
+mod rl_dominators;
+mod rl_entry_points;
 pub mod rl_graph;
+mod rl_hotspots;
+mod rl_impact;
+mod rl_layering;
+mod rl_ownership;
+mod rl_ownership_cost;
+mod rl_paths;
 mod rl_petgraph;
+mod rl_receiver_report;
+mod rl_rules;
+mod rl_sccs;
 mod rl_visitor;
 
+pub use rl_borrow_suggester::RLBorrowSuggester;
+pub use rl_borrowck::override_queries;
+pub use rl_by_type::RLArgType;
+pub use rl_condensed::{Granularity, RLCondenser};
+pub use rl_context::{CallSourceKind, EntryPointKind};
+pub use rl_copy_suggester::RLCopySuggester;
+pub use rl_dominators::RLDominatorTreeNode;
+pub use rl_hotspots::RLCentrality;
+pub use rl_impact::RLDiff;
+pub use rl_layering::{RLLayeringChecker, RLLayeringRules};
+pub use rl_ownership::RLOwnershipTracer;
+pub use rl_ownership_cost::RLOwnershipCost;
+pub use rl_paths::RLPathFinder;
+pub use rl_receiver_report::RLReceiverReporter;
+pub use rl_rules::{RLRuleChecker, RLRules};
+pub use rl_sccs::RLScc;
+
 use super::{
     utils::{MERGED_FILE_NAME, RL_SERDE_FOLDER},
     Analyzer,
 };
+use rl_borrow_suggester::RLReadOnlyParam;
+use rl_borrowck::RLBorrowckFacts;
+use rl_entry_points::collect_entry_points;
 use rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use rl_ownership::RLOwnershipFlow;
 use rl_visitor::RLVisitor;
 
+use rustc_hash::FxHashMap;
 use rustc_hir::def_id::LOCAL_CRATE;
 use rustc_middle::ty;
 use serde::{de::DeserializeOwned, Serialize};
@@ -66,20 +106,65 @@ where
         // MyStruct::{constructor#0})
         // MyEnum::Variant::{constructor#0})
         // ```
+        //
+        // The borrow checker facts must be collected before visiting the bodies, since
+        // `instance_mir` steals the MIR needed by the borrow checker.
+        let mut borrowck_facts = if self.analyzer.cli_args.borrowck_facts {
+            self.analyzer
+                .tcx
+                .hir_body_owners()
+                .filter_map(|local_def_id| {
+                    RLBorrowckFacts::collect(self.analyzer.tcx, local_def_id).map(|facts| {
+                        facts
+                            .map(|facts| (local_def_id, facts))
+                            .unwrap_or_else(|error| self.analyzer.tcx.dcx().fatal(error))
+                    })
+                })
+                .collect::<FxHashMap<_, _>>()
+        } else {
+            FxHashMap::default()
+        };
+
         for local_def_id in self.analyzer.tcx.hir_body_owners() {
+            let body = self
+                .analyzer
+                .tcx
+                .instance_mir(ty::InstanceKind::Item(local_def_id.to_def_id()));
+
             // Visit the body of the `local_def_id`
-            visitor.visit_local_def_id(
-                local_def_id,
-                self.analyzer
-                    .tcx
-                    .instance_mir(ty::InstanceKind::Item(local_def_id.to_def_id())),
-            );
+            visitor.visit_local_def_id(local_def_id, body);
+
+            if self.analyzer.cli_args.print_ownership_chains {
+                if let Some(flow) = RLOwnershipFlow::collect(self.analyzer.tcx, local_def_id, body)
+                {
+                    visitor.set_ownership_flow(local_def_id, flow);
+                }
+            }
+
+            if self.analyzer.cli_args.suggest_borrows {
+                visitor.set_read_only_params(
+                    local_def_id,
+                    RLReadOnlyParam::collect(self.analyzer.tcx, local_def_id, body),
+                );
+            }
+
+            if let Some(facts) = borrowck_facts.remove(&local_def_id) {
+                visitor.set_borrowck_facts(local_def_id, facts);
+            }
 
             // TODO: Check if the body has some promoted MIR.
             // It is not clear if analyzing the promoted MIR is necessary.
             let _promoted_mir = self.analyzer.tcx.promoted_mir(local_def_id.to_def_id());
         }
 
+        for (local_def_id, entry_point) in collect_entry_points(self.analyzer.tcx) {
+            visitor.set_entry_point(local_def_id, entry_point);
+        }
+
+        if self.analyzer.cli_args.over_approximate_unknown_calls {
+            visitor.link_unknown_call_sites();
+        }
+
         visitor.rl_graph().clone()
     }
 
@@ -87,14 +172,21 @@ where
     // The merging is done by the `merge_all_rl_graphs` method, which is used only in tests.
     fn serialize_rl_graph_to_file(&self, rl_graph: &G) {
         std::fs::create_dir_all(RL_SERDE_FOLDER).expect("Failed to create folder");
-        let file_name = format!("{}/{}.rlg", RL_SERDE_FOLDER, self.krate_name);
+        // With `--tests`, a library is compiled also as a test harness, which is a different
+        // crate (its functions have a different `DefId` in the dependent crates), so it must
+        // not overwrite the graph of the library.
+        let file_name = if self.analyzer.tcx.sess.is_test_crate() {
+            format!("{}/{}.test.rlg", RL_SERDE_FOLDER, self.krate_name)
+        } else {
+            format!("{}/{}.rlg", RL_SERDE_FOLDER, self.krate_name)
+        };
         let file = std::fs::File::create(file_name).expect("Failed to create file");
         serde_json::to_writer(file, rl_graph).expect("Failed to serialize RLGraph");
     }
 
     /// Merge all the RLGraphs in the folder `RL_SERDE_FOLDER` into a single RLGraph.
-    /// The merged RLGraph is serialized into a file named `MERGED_FILE_NAME`.
-    pub fn merge_all_rl_graphs() {
+    /// The merged RLGraph is serialized into a file named `MERGED_FILE_NAME` and returned.
+    pub fn merge_all_rl_graphs() -> G {
         let mut merged_rl_graph: G = G::default();
         let mut rl_graphs = std::fs::read_dir(RL_SERDE_FOLDER)
             .expect("Failed to read folder")
@@ -112,6 +204,7 @@ where
         let file_name = format!("{}/{}.rlg", RL_SERDE_FOLDER, MERGED_FILE_NAME);
         let file = std::fs::File::create(file_name).expect("Failed to create file");
         serde_json::to_writer(file, &merged_rl_graph).expect("Failed to serialize RLGraph");
+        merged_rl_graph
     }
 
     pub fn deserialized_rl_graph_from_file(file_path: &str) -> G {
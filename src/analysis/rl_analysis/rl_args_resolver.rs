@@ -1,8 +1,10 @@
-use rustc_middle::mir;
+use rustc_middle::{mir, ty};
 use serde::Serialize;
 
 use super::{
-    rl_context::{CallKind, MutabilityKind, OperandKind, RLContext, RLTyKind},
+    rl_context::{
+        ArgUsageKind, CallKind, MutabilityKind, OperandKind, RLArgTy, RLContext, RLTyKind, RLValue,
+    },
     rl_graph::RLGraph,
 };
 
@@ -29,6 +31,52 @@ where
         (call_kind.clone(), self.resolve_arg_types(args))
     }
 
+    /// Resolve the concrete type of each argument, without the references,
+    /// and how the value is passed (e.g., a clone or a mutable borrow).
+    pub fn resolve_arg_tys(
+        &self,
+        tcx: ty::TyCtxt<'tcx>,
+        args: &'a [mir::Operand<'tcx>],
+    ) -> Vec<RLArgTy> {
+        let body = self.ctx.current_body.unwrap();
+        let typing_env = ty::TypingEnv::post_analysis(tcx, self.ctx.current_local_def_id.unwrap());
+        args.iter()
+            .map(|arg| {
+                let ty = arg.ty(body, tcx);
+                let usage = match arg {
+                    mir::Operand::Constant(_) => ArgUsageKind::Constant,
+                    mir::Operand::Move(place) | mir::Operand::Copy(place)
+                        if self.is_cloned(place) =>
+                    {
+                        ArgUsageKind::Clone
+                    }
+                    _ => match ty.kind() {
+                        ty::TyKind::Ref(_, _, ty::Mutability::Mut) => ArgUsageKind::MutBorrow,
+                        ty::TyKind::Ref(_, _, ty::Mutability::Not) => ArgUsageKind::Borrow,
+                        // The temporaries of `Copy` types are moved too.
+                        _ if matches!(arg, mir::Operand::Move(_))
+                            && !tcx.type_is_copy_modulo_regions(typing_env, ty) =>
+                        {
+                            ArgUsageKind::Move
+                        }
+                        _ => ArgUsageKind::Copy,
+                    },
+                };
+                let ty = ty.peel_refs();
+                RLArgTy::new(ty.to_string(), ty.ty_adt_def().map(|adt| adt.did()), usage)
+            })
+            .collect()
+    }
+
+    /// Whether the place is the result of a call to `Clone::clone`.
+    fn is_cloned(&self, place: &mir::Place<'tcx>) -> bool {
+        place.projection.is_empty()
+            && matches!(
+                self.ctx.map_place_rlvalue.get(&place.local),
+                Some(Some(RLValue::TermCallClone(_)))
+            )
+    }
+
     fn resolve_arg_types(
         &self,
         args: &'a [mir::Operand<'tcx>],
@@ -0,0 +1,237 @@
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_middle::{
+    mir::{
+        self,
+        visit::{MutatingUseContext, NonMutatingUseContext, PlaceContext, Visitor},
+    },
+    ty::{self, TyCtxt},
+};
+use rustc_span::{
+    def_id::{DefId, LocalDefId},
+    sym,
+};
+use serde::{Deserialize, Serialize};
+
+use super::rl_context::{ArgUsageKind, CallKind, OperandKind};
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// A call site passing a value to a parameter which could be borrowed.
+#[derive(Debug, Clone)]
+pub struct RLBorrowCallSite {
+    caller: String,
+    /// The argument is moved (`ArgUsageKind::Move`), or it is the result of a `clone`
+    /// whose only purpose is to be moved into the call (`ArgUsageKind::Clone`).
+    usage: ArgUsageKind,
+}
+
+/// A by-value parameter of non-`Copy` type which is only read by its function,
+/// with the reference type it could take instead and the call sites paying for the move.
+#[derive(Debug, Clone)]
+pub struct RLBorrowSuggestion {
+    function: String,
+    param: RLReadOnlyParam,
+    call_sites: Vec<RLBorrowCallSite>,
+}
+
+impl std::fmt::Display for RLBorrowSuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` takes `{}: {}` by value but only reads it, consider `{}`",
+            self.function, self.param.name, self.param.ty, self.param.suggested_ty
+        )?;
+        for call_site in &self.call_sites {
+            write!(f, "\n    <- `{}` ({:?})", call_site.caller, call_site.usage)?;
+        }
+        Ok(())
+    }
+}
+
+/// A by-value parameter of non-`Copy` type which is only used through shared references
+/// by its function: it is never moved out, never mutated and never stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RLReadOnlyParam {
+    /// The position of the parameter, i.e., of the argument of the calls.
+    index: usize,
+    name: String,
+    ty: String,
+    /// The reference type to take instead, e.g., `&str` instead of `String`.
+    suggested_ty: String,
+}
+
+impl RLReadOnlyParam {
+    /// Collect the read-only parameters of the body of `local_def_id`.
+    ///
+    /// The closures and the methods of the traits are skipped, since their signature
+    /// cannot be changed.
+    pub fn collect<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        local_def_id: LocalDefId,
+        body: &mir::Body<'tcx>,
+    ) -> Vec<Self> {
+        let def_id = local_def_id.to_def_id();
+        if !has_free_signature(tcx, def_id) {
+            return Vec::new();
+        }
+        let typing_env = ty::TypingEnv::post_analysis(tcx, def_id);
+        let mut params = Vec::new();
+        for param in body.args_iter() {
+            let ty = body.local_decls[param].ty;
+            if ty.is_ref() || ty.is_raw_ptr() || tcx.type_is_copy_modulo_regions(typing_env, ty) {
+                continue;
+            }
+            let mut usage = ParamUsage {
+                param,
+                read: false,
+                consumed: false,
+            };
+            usage.visit_body(body);
+            if !usage.read || usage.consumed {
+                continue;
+            }
+
+            let name = param_name(body, param);
+            let suggested_ty = if name == "self" {
+                "&self".to_string()
+            } else {
+                suggested_ty(tcx, ty)
+            };
+            params.push(Self {
+                index: param.as_usize() - 1,
+                name,
+                ty: ty.to_string(),
+                suggested_ty,
+            });
+        }
+        params
+    }
+}
+
+/// `RLBorrowSuggester` reports the read-only parameters of the functions of the graph
+/// (see `RLReadOnlyParam`), with the calls moving a value into them, i.e., the edges
+/// whose argument is `OperandKind::Move`.
+pub struct RLBorrowSuggester<'tcx, 'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    tcx: TyCtxt<'tcx>,
+    rl_graph: &'a G,
+}
+
+impl<'tcx, 'a, G> RLBorrowSuggester<'tcx, 'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    pub fn new(tcx: TyCtxt<'tcx>, rl_graph: &'a G) -> Self {
+        Self { tcx, rl_graph }
+    }
+
+    pub fn suggestions(&self) -> Vec<RLBorrowSuggestion> {
+        let mut calls: FxHashMap<RLIndex, Vec<(RLIndex, &RLEdge)>> = FxHashMap::default();
+        for (caller, callee, edge) in self.rl_graph.rl_edges() {
+            if matches!(edge.call_kind(), CallKind::Function | CallKind::Method) {
+                calls.entry(callee).or_default().push((caller, edge));
+            }
+        }
+
+        let mut suggestions = Vec::new();
+        for (index, node) in self.rl_graph.rl_nodes() {
+            for param in node.read_only_params() {
+                let call_sites = calls
+                    .get(&index)
+                    .into_iter()
+                    .flatten()
+                    .filter(|(_, edge)| {
+                        edge.arg_weights()
+                            .get(param.index)
+                            .is_some_and(|(operand, _, _)| *operand == OperandKind::Move)
+                    })
+                    .map(|(caller, edge)| RLBorrowCallSite {
+                        caller: self
+                            .tcx
+                            .def_path_str(self.rl_graph.rl_node(*caller).def_id()),
+                        usage: match edge.arg_tys().get(param.index) {
+                            Some(arg_ty) if arg_ty.usage() == ArgUsageKind::Clone => {
+                                ArgUsageKind::Clone
+                            }
+                            _ => ArgUsageKind::Move,
+                        },
+                    })
+                    .collect();
+                suggestions.push(RLBorrowSuggestion {
+                    function: self.tcx.def_path_str(node.def_id()),
+                    param: param.clone(),
+                    call_sites,
+                });
+            }
+        }
+        suggestions
+    }
+}
+
+/// Whether the signature of the function can be changed, i.e., it is a function or
+/// an inherent method, not a closure or the method of a trait.
+fn has_free_signature(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    match tcx.def_kind(def_id) {
+        DefKind::Fn => true,
+        DefKind::AssocFn => match tcx.impl_of_method(def_id) {
+            Some(impl_def_id) => tcx.trait_id_of_impl(impl_def_id).is_none(),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// The reference type to take instead of `ty`, e.g., `&str` instead of `String`.
+fn suggested_ty<'tcx>(tcx: TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> String {
+    match ty.kind() {
+        ty::TyKind::Adt(adt_def, _) if tcx.lang_items().string() == Some(adt_def.did()) => {
+            "&str".to_string()
+        }
+        ty::TyKind::Adt(adt_def, args) if tcx.is_diagnostic_item(sym::Vec, adt_def.did()) => {
+            format!("&[{}]", args.type_at(0))
+        }
+        _ => format!("&{}", ty),
+    }
+}
+
+fn param_name(body: &mir::Body<'_>, param: mir::Local) -> String {
+    body.var_debug_info
+        .iter()
+        .find_map(|var_debug_info| match var_debug_info.value {
+            mir::VarDebugInfoContents::Place(place) if place.as_local() == Some(param) => {
+                Some(var_debug_info.name.to_string())
+            }
+            _ => None,
+        })
+        .unwrap_or_else(|| format!("{:?}", param))
+}
+
+/// Classify the uses of a parameter: it is read if it is borrowed or copied (e.g., one of
+/// its `Copy` fields), it is consumed if it is moved, mutated or stored.
+/// Its drop at the end of the function is neither of them.
+struct ParamUsage {
+    param: mir::Local,
+    read: bool,
+    consumed: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for ParamUsage {
+    fn visit_place(
+        &mut self,
+        place: &mir::Place<'tcx>,
+        context: PlaceContext,
+        _location: mir::Location,
+    ) {
+        if place.local != self.param {
+            return;
+        }
+        match context {
+            PlaceContext::NonMutatingUse(NonMutatingUseContext::Move) => self.consumed = true,
+            PlaceContext::NonMutatingUse(_) => self.read = true,
+            PlaceContext::MutatingUse(MutatingUseContext::Drop) | PlaceContext::NonUse(_) => {}
+            PlaceContext::MutatingUse(_) => self.consumed = true,
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use rustc_borrowck::consumers::{self, BodyWithBorrowckFacts, ConsumerOptions, TwoPhaseActivation};
+use rustc_hash::FxHashMap;
+use rustc_middle::{
+    mir::{self, visit::Visitor},
+    query::Providers,
+    ty::{self, TyCtxt},
+    util,
+};
+use rustc_session::Session;
+use rustc_span::def_id::LocalDefId;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+
+thread_local! {
+    /// The facts collected by `mir_borrowck` for each function and closure body of the crate.
+    static BORROWCK_FACTS: RefCell<FxHashMap<LocalDefId, Result<RLBorrowckFacts, String>>> =
+        RefCell::new(FxHashMap::default());
+}
+
+/// Override the `mir_borrowck` query to collect the facts of the borrow checker of each body
+/// when it is borrow checked.
+///
+/// The MIR needed by the borrow checker is stolen once the body is borrow checked, which
+/// can happen before the analysis runs (e.g., a `const fn` evaluated during type checking),
+/// so the facts cannot be reliably computed afterwards.
+pub fn override_queries(_session: &Session, providers: &mut util::Providers) {
+    providers.queries.mir_borrowck = mir_borrowck;
+}
+
+fn mir_borrowck(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> &mir::BorrowCheckResult<'_> {
+    if tcx.def_kind(local_def_id).is_fn_like() {
+        let body_with_facts = consumers::get_body_with_borrowck_facts(
+            tcx,
+            local_def_id,
+            ConsumerOptions::PoloniusInputFacts,
+        );
+        let facts = RLBorrowckFacts::from_body(tcx, local_def_id, &body_with_facts);
+        BORROWCK_FACTS.with(|borrowck_facts| {
+            borrowck_facts.borrow_mut().insert(local_def_id, facts);
+        });
+    }
+
+    let mut providers = Providers::default();
+    rustc_borrowck::provide(&mut providers);
+    (providers.mir_borrowck)(tcx, local_def_id)
+}
+
+/// `RLBorrowckFacts` contains the ownership metrics of a function computed from the
+/// facts of the borrow checker:
+/// - the number of shared and mutable borrows (reborrows included, except the shared
+///   reborrows through a shared reference, which the borrow checker does not track);
+/// - the number of reborrows, i.e., borrows of a place behind a reference;
+/// - the number of two-phase borrows (e.g., the `&mut v` of `v.push(v.len())`);
+/// - the number of moves out of a user variable or an argument, or out of one of their
+///   fields (the moves of the temporaries introduced by the compiler are not counted);
+/// - the number of borrows whose lifetime outlives the function, i.e., the lifetime
+///   must outlive one of the lifetime parameters of the function (or `'static`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RLBorrowckFacts {
+    shared_borrows: usize,
+    mut_borrows: usize,
+    reborrows: usize,
+    two_phase_borrows: usize,
+    moves: usize,
+    outliving_borrows: usize,
+}
+
+impl RLBorrowckFacts {
+    /// The facts of the borrow checker on the body of `local_def_id`.
+    ///
+    /// The `mir_borrowck` query must be overridden by `override_queries`.
+    /// It returns `None` if the body is not a function or a closure, and an error if the
+    /// facts of the body were not collected.
+    pub fn collect(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> Option<Result<Self, String>> {
+        if !tcx.def_kind(local_def_id).is_fn_like() {
+            return None;
+        }
+
+        tcx.ensure_done().mir_borrowck(local_def_id);
+        let facts = BORROWCK_FACTS
+            .with(|borrowck_facts| borrowck_facts.borrow_mut().remove(&local_def_id))
+            .unwrap_or_else(|| {
+                Err(format!(
+                    "The borrow checker facts of `{}` were not collected",
+                    tcx.def_path_str(local_def_id)
+                ))
+            });
+        Some(facts)
+    }
+
+    fn from_body(
+        tcx: TyCtxt<'_>,
+        local_def_id: LocalDefId,
+        body_with_facts: &BodyWithBorrowckFacts<'_>,
+    ) -> Result<Self, String> {
+        let region_inference_context = &body_with_facts.region_inference_context;
+        // The universal regions are `'static`, the lifetime parameters of the function
+        // and, as the last one, the region of the function body.
+        // A borrow outlives the function if it outlives a universal region other than
+        // the one of the function body.
+        let mut universal_regions = body_with_facts
+            .input_facts
+            .as_ref()
+            .ok_or_else(|| {
+                format!(
+                    "The Polonius input facts of `{}` were not computed",
+                    tcx.def_path_str(local_def_id)
+                )
+            })?
+            .universal_region
+            .iter()
+            .map(|region| ty::RegionVid::from(*region))
+            .collect::<Vec<_>>();
+        universal_regions.sort();
+        universal_regions.pop();
+
+        let mut facts = Self::default();
+        for borrow in body_with_facts.borrow_set.location_map().values() {
+            match borrow.kind() {
+                mir::BorrowKind::Shared => facts.shared_borrows += 1,
+                mir::BorrowKind::Mut { .. } => facts.mut_borrows += 1,
+                // The fake borrows are introduced by the compiler for the match guards.
+                mir::BorrowKind::Fake(_) => continue,
+            }
+            if borrow.borrowed_place().is_indirect() {
+                facts.reborrows += 1;
+            }
+            if borrow.activation_location() != TwoPhaseActivation::NotTwoPhase {
+                facts.two_phase_borrows += 1;
+            }
+            if universal_regions
+                .iter()
+                .any(|region| region_inference_context.eval_outlives(borrow.region(), *region))
+            {
+                facts.outliving_borrows += 1;
+            }
+        }
+
+        let mut move_counter = MoveCounter {
+            body: &body_with_facts.body,
+            moves: 0,
+        };
+        move_counter.visit_body(&body_with_facts.body);
+        facts.moves = move_counter.moves;
+
+        Ok(facts)
+    }
+
+    pub fn shared_borrows(&self) -> usize {
+        self.shared_borrows
+    }
+
+    pub fn mut_borrows(&self) -> usize {
+        self.mut_borrows
+    }
+
+    pub fn reborrows(&self) -> usize {
+        self.reborrows
+    }
+
+    pub fn two_phase_borrows(&self) -> usize {
+        self.two_phase_borrows
+    }
+
+    pub fn moves(&self) -> usize {
+        self.moves
+    }
+
+    pub fn outliving_borrows(&self) -> usize {
+        self.outliving_borrows
+    }
+}
+
+/// Count the moves out of a user variable or an argument (or out of one of their fields).
+struct MoveCounter<'a, 'tcx> {
+    body: &'a mir::Body<'tcx>,
+    moves: usize,
+}
+
+impl<'tcx> Visitor<'tcx> for MoveCounter<'_, 'tcx> {
+    fn visit_operand(&mut self, operand: &mir::Operand<'tcx>, location: mir::Location) {
+        if let mir::Operand::Move(place) = operand {
+            let local_decl = &self.body.local_decls[place.local];
+            if local_decl.is_user_variable()
+                || self.body.local_kind(place.local) == mir::LocalKind::Arg
+            {
+                self.moves += 1;
+            }
+        }
+        self.super_operand(operand, location);
+    }
+}
@@ -0,0 +1,120 @@
+use rustc_data_structures::fx::FxIndexMap;
+use std::collections::BTreeMap;
+
+use super::rl_context::ArgUsageKind;
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// A call site `(caller, callee)` passing arguments of a type, with the number of them.
+#[derive(Debug, Clone)]
+pub struct RLArgCallSite {
+    caller: RLIndex,
+    callee: RLIndex,
+    args: usize,
+}
+
+impl RLArgCallSite {
+    pub fn caller(&self) -> RLIndex {
+        self.caller
+    }
+
+    pub fn callee(&self) -> RLIndex {
+        self.callee
+    }
+
+    pub fn args(&self) -> usize {
+        self.args
+    }
+}
+
+/// How the arguments of a type are passed (e.g., moved, cloned or borrowed), with the call
+/// sites passing them, sorted by decreasing number of arguments.
+#[derive(Debug, Clone)]
+pub struct RLArgUsage {
+    usage: ArgUsageKind,
+    call_sites: Vec<RLArgCallSite>,
+}
+
+impl RLArgUsage {
+    pub fn usage(&self) -> ArgUsageKind {
+        self.usage
+    }
+
+    pub fn call_sites(&self) -> &[RLArgCallSite] {
+        &self.call_sites
+    }
+
+    /// The number of arguments passed this way.
+    pub fn args(&self) -> usize {
+        self.call_sites.iter().map(RLArgCallSite::args).sum()
+    }
+}
+
+/// A concrete type passed as argument in the graph, with the ways it is passed
+/// (see `RLEdge::arg_tys`).
+#[derive(Debug, Clone)]
+pub struct RLArgType {
+    ty: String,
+    usages: Vec<RLArgUsage>,
+}
+
+impl RLArgType {
+    /// The types passed as arguments in the graph, the most used ones first.
+    pub fn collect<G>(rl_graph: &G) -> Vec<Self>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let mut by_type: FxIndexMap<&str, BTreeMap<ArgUsageKind, FxIndexMap<_, usize>>> =
+            FxIndexMap::default();
+        for (caller, callee, edge) in rl_graph.rl_edges() {
+            for arg_ty in edge.arg_tys() {
+                *by_type
+                    .entry(arg_ty.ty())
+                    .or_default()
+                    .entry(arg_ty.usage())
+                    .or_default()
+                    .entry((caller, callee))
+                    .or_default() += 1;
+            }
+        }
+
+        let mut arg_types = by_type
+            .into_iter()
+            .map(|(ty, usages)| {
+                let usages = usages
+                    .into_iter()
+                    .map(|(usage, call_sites)| {
+                        let mut call_sites = call_sites
+                            .into_iter()
+                            .map(|((caller, callee), args)| RLArgCallSite {
+                                caller,
+                                callee,
+                                args,
+                            })
+                            .collect::<Vec<_>>();
+                        call_sites.sort_by_key(|call_site| std::cmp::Reverse(call_site.args));
+                        RLArgUsage { usage, call_sites }
+                    })
+                    .collect();
+                RLArgType {
+                    ty: ty.to_string(),
+                    usages,
+                }
+            })
+            .collect::<Vec<_>>();
+        arg_types.sort_by_key(|arg_type| std::cmp::Reverse(arg_type.args()));
+        arg_types
+    }
+
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    pub fn usages(&self) -> &[RLArgUsage] {
+        &self.usages
+    }
+
+    /// The number of arguments of the type.
+    pub fn args(&self) -> usize {
+        self.usages.iter().map(RLArgUsage::args).sum()
+    }
+}
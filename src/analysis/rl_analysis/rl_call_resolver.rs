@@ -417,6 +417,7 @@ where
                         let assoc_item = self.analyzer.tcx.associated_item(*def_id);
                         if assoc_item.fn_has_self_parameter {
                             // Check if the def_id is the std::ops::Fn<(T,)>::call
+                            // (or std::ops::FnMut::call_mut, std::ops::FnOnce::call_once)
                             // The call is in the form:
                             // bb0: {
                             //     _1 = T { value: const 10_i32 };
@@ -432,6 +433,7 @@ where
                             if krate_name == rustc_span::Symbol::intern("core") {
                                 let fun_name = self.analyzer.tcx.def_path_str(*def_id);
                                 if fun_name == "std::ops::Fn::call"
+                                    || fun_name == "std::ops::FnMut::call_mut"
                                     || fun_name == "std::ops::FnOnce::call_once"
                                 {
                                     if let Operand::Constant(const_operand) = &args[0].node {
@@ -0,0 +1,76 @@
+use serde::de::DeserializeOwned;
+
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// A call of the graph, with the paths and the crates of its endpoints, as checked by the
+/// layering rules (see `RLLayeringRules`) and the user-defined rules (see `RLRules`).
+pub struct RLCall<'a> {
+    caller: &'a str,
+    callee: &'a str,
+    caller_crate: &'a str,
+    callee_crate: &'a str,
+    edge: &'a RLEdge,
+}
+
+impl<'a> RLCall<'a> {
+    /// The calls of the graph.
+    /// The statically unknown call sites are skipped, since their callee is unknown.
+    pub fn collect<G>(rl_graph: &'a G) -> Vec<Self>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        rl_graph
+            .rl_edges()
+            .into_iter()
+            .filter_map(|(caller, callee, edge)| {
+                let (caller, callee) = (rl_graph.rl_node(caller), rl_graph.rl_node(callee));
+                Some(Self {
+                    caller: caller.def_path()?,
+                    callee: callee.def_path()?,
+                    caller_crate: caller.crate_name()?,
+                    callee_crate: callee.crate_name()?,
+                    edge,
+                })
+            })
+            .collect()
+    }
+
+    /// The path of the caller with its crate, e.g., `app::domain::check`.
+    pub fn caller(&self) -> &'a str {
+        self.caller
+    }
+
+    /// The path of the callee with its crate.
+    pub fn callee(&self) -> &'a str {
+        self.callee
+    }
+
+    pub fn caller_crate(&self) -> &'a str {
+        self.caller_crate
+    }
+
+    pub fn callee_crate(&self) -> &'a str {
+        self.callee_crate
+    }
+
+    pub fn edge(&self) -> &'a RLEdge {
+        self.edge
+    }
+}
+
+/// Read the rules from a TOML file, failing with an error naming the file if it cannot be
+/// read or parsed.
+pub fn rules_from_file<T: DeserializeOwned>(file_path: &str) -> Result<T, String> {
+    let rules = std::fs::read_to_string(file_path)
+        .map_err(|error| format!("Failed to read the rule file `{}`: {}", file_path, error))?;
+    toml::from_str(&rules)
+        .map_err(|error| format!("Failed to parse the rule file `{}`: {}", file_path, error))
+}
+
+/// Whether the `path` is the item of the `pattern` or is defined inside it, e.g.,
+/// `app::legacy` and `app::legacy::*` match `app::legacy::parse`.
+pub fn matches_path(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.strip_suffix("::*").unwrap_or(pattern);
+    path.strip_prefix(pattern)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+}
@@ -0,0 +1,210 @@
+use rustc_hir::def::DefKind;
+use rustc_hir::definitions::DefPathDataName;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::def_id::DefId;
+use rustworkx_core::petgraph::graph;
+use serde::Serialize;
+
+use super::rl_graph::{RLGraphNode, RLNode};
+
+/// The level at which the nodes of the graph are printed.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum Granularity {
+    /// A node for each function (or closure), i.e., the graph is not condensed.
+    #[default]
+    Fn,
+    /// A node for each type with an `impl` (inherent or trait), collecting its methods.
+    /// The functions outside the `impl`s are not condensed.
+    Impl,
+    /// A node for each module.
+    Module,
+    /// A node for each crate.
+    Crate,
+}
+
+/// A group of functions of the condensed graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct RLGroupNode {
+    label: String,
+    functions: usize,
+}
+
+/// The calls between two groups of functions of the condensed graph.
+#[derive(Debug, Clone, Serialize)]
+pub struct RLGroupEdge {
+    calls: usize,
+    /// The sum of the ownership scores of the calls (see `RLEdge::ownership_score`).
+    ownership_score: usize,
+}
+
+/// The graph whose nodes are groups of functions (e.g., the modules), with a single edge
+/// between two groups aggregating all the calls between their functions.
+/// The calls between the functions of the same group are aggregated in a self-loop.
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(transparent)]
+pub struct RLCondensedGraph {
+    graph: graph::DiGraph<RLGroupNode, RLGroupEdge>,
+}
+
+impl RLCondensedGraph {
+    /// Add the node of the `label` group if needed, counting one more function in it.
+    pub fn add_function(&mut self, label: String) -> graph::NodeIndex {
+        match self
+            .graph
+            .node_indices()
+            .find(|node| self.graph[*node].label == label)
+        {
+            Some(node) => {
+                self.graph[node].functions += 1;
+                node
+            }
+            None => self.graph.add_node(RLGroupNode {
+                label,
+                functions: 1,
+            }),
+        }
+    }
+
+    /// Add a call between two groups, aggregating it with the previous ones.
+    pub fn add_call(
+        &mut self,
+        source: graph::NodeIndex,
+        target: graph::NodeIndex,
+        ownership_score: usize,
+    ) {
+        match self.graph.find_edge(source, target) {
+            Some(edge) => {
+                self.graph[edge].calls += 1;
+                self.graph[edge].ownership_score += ownership_score;
+            }
+            None => {
+                self.graph.add_edge(
+                    source,
+                    target,
+                    RLGroupEdge {
+                        calls: 1,
+                        ownership_score,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Return the cycles between the groups, i.e., the strongly connected components with
+    /// more than one group, each one with the labels of its groups sorted.
+    /// The calls inside a group are not cycles.
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let mut cycles = rustworkx_core::petgraph::algo::tarjan_scc(&self.graph)
+            .into_iter()
+            .filter(|scc| scc.len() > 1)
+            .map(|scc| {
+                let mut labels = scc
+                    .into_iter()
+                    .map(|node| self.graph[node].label.clone())
+                    .collect::<Vec<_>>();
+                labels.sort();
+                labels
+            })
+            .collect::<Vec<_>>();
+        cycles.sort();
+        cycles
+    }
+
+    pub fn as_dot_str(&self) -> String {
+        use rustworkx_core::petgraph::dot::{Config, Dot};
+
+        format!(
+            "{:?}",
+            Dot::with_attr_getters(
+                &self.graph,
+                &[Config::NodeNoLabel, Config::EdgeNoLabel],
+                &|_g, e| format!(
+                    "label=\"calls: {}, ownership score: {}\"",
+                    e.weight().calls,
+                    e.weight().ownership_score
+                ),
+                &|_g, (_, node)| format!(
+                    "label=\"{} ({} functions)\"",
+                    node.label.replace('"', "\\\""),
+                    node.functions
+                ),
+            )
+        )
+    }
+}
+
+/// `RLCondenser` finds the group of each node of the graph for a `Granularity`.
+/// The groups are read from the paths stored on the nodes (see `RLNode::set_def_path`),
+/// so the merged graph of the workspace can be condensed too.
+pub struct RLCondenser {
+    granularity: Granularity,
+}
+
+impl RLCondenser {
+    pub fn new(granularity: Granularity) -> Self {
+        Self { granularity }
+    }
+
+    /// The label of the group of the node, e.g., `temp::parser` for a function of the
+    /// module `parser` of the crate `temp`.
+    /// The statically unknown call sites are grouped by the signature of the callee.
+    pub fn group_of(&self, node: &RLNode) -> String {
+        if let Some(fn_sig) = node.unknown_fn_sig() {
+            return match self.granularity {
+                Granularity::Fn => node.def_id_str(),
+                _ => format!("STATICALLY_UNKNOWN: {}", fn_sig),
+            };
+        }
+        let group = match self.granularity {
+            Granularity::Fn => None,
+            Granularity::Impl => node.impl_path(),
+            Granularity::Module => node.module_path(),
+            Granularity::Crate => node.crate_name(),
+        };
+        group.map_or_else(|| node.def_id_str(), str::to_string)
+    }
+}
+
+/// The path of the type of the `impl` containing the `def_id` (e.g., `temp::parser::Parser`
+/// for its methods and their closures), or the path of the `def_id` outside the `impl`s.
+pub fn impl_path(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let def_id = tcx.typeck_root_def_id(def_id);
+    match tcx.opt_parent(def_id) {
+        Some(parent) if matches!(tcx.def_kind(parent), DefKind::Impl { .. }) => {
+            let self_ty = tcx.type_of(parent).instantiate_identity();
+            match self_ty.kind() {
+                ty::TyKind::Adt(adt_def, _) => def_path(tcx, adt_def.did()),
+                _ => format!("{}::{}", tcx.crate_name(def_id.krate), self_ty),
+            }
+        }
+        _ => def_path(tcx, def_id),
+    }
+}
+
+/// The path of the `def_id` with its crate, e.g., `temp::parser::parse`.
+/// Only the anonymous items keep their disambiguator (e.g., `temp::main::{closure#0}`),
+/// so the `#[test]` function `temp::tests::check#1` is `temp::tests::check`.
+pub fn def_path(tcx: TyCtxt<'_>, def_id: DefId) -> String {
+    let mut def_path = tcx.crate_name(def_id.krate).to_string();
+    for data in tcx.def_path(def_id).data {
+        match data.data.name() {
+            DefPathDataName::Named(name) => def_path.push_str(&format!("::{}", name)),
+            DefPathDataName::Anon { namespace } => {
+                def_path.push_str(&format!("::{{{}#{}}}", namespace, data.disambiguator))
+            }
+        }
+    }
+    def_path
+}
+
+/// The innermost module containing the `def_id`.
+pub fn module_of(tcx: TyCtxt<'_>, def_id: DefId) -> DefId {
+    let mut module = def_id;
+    while let Some(parent) = tcx.opt_parent(module) {
+        module = parent;
+        if tcx.def_kind(module) == DefKind::Mod {
+            break;
+        }
+    }
+    module
+}
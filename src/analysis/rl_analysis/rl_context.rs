@@ -1,13 +1,15 @@
-use super::rl_graph::RLGraph;
+use super::rl_control_flow::RLControlFlow;
+use super::rl_graph::{option_def_id_serde, RLGraph};
 use super::rl_graph::{RLEdge, RLIndex, RLNode};
+use rustc_data_structures::fx::FxIndexMap;
 use rustc_hash::{FxHashMap, FxHashSet};
 use rustc_middle::mir::{self, Promoted};
-use rustc_middle::ty;
+use rustc_middle::ty::{self, TypeVisitableExt};
 use rustc_span::def_id::DefId;
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum CallKind {
     Clone,
     StaticMut,
@@ -17,6 +19,18 @@ pub enum CallKind {
     Closure,
     Method,
     StaticallyUnknown,
+    /// A closure (or a function item) passed to a non-local function
+    /// (e.g., `Iterator::map`, `std::thread::spawn`) which actually calls it.
+    Indirect,
+    /// A drop of a value whose drop glue reaches a `Drop::drop` implementation.
+    Drop,
+    /// An edge from a statically unknown call site to a function (or closure)
+    /// whose address is taken and whose signature matches the one of the call site.
+    OverApproximated,
+    /// A read of a `static`, a `static mut` or a `thread_local!`.
+    Reads,
+    /// A write of a `static`, a `static mut` or a `thread_local!`.
+    Writes,
     Unknown,
 }
 
@@ -29,6 +43,40 @@ impl From<ty::Mutability> for CallKind {
     }
 }
 
+/// Where a call comes from in the source code.
+#[derive(Debug, Copy, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub enum CallSourceKind {
+    /// A call written by the user.
+    #[default]
+    User,
+    /// An overloaded operator (e.g., `Add::add`, `Index::index`, `Deref::deref`),
+    /// including the implicit dereferences of the auto-deref.
+    Operator,
+    /// The desugaring of the `?` operator (e.g., `Try::branch`, `FromResidual::from_residual`).
+    QuestionMark,
+    /// The desugaring of a `for` loop (e.g., `IntoIterator::into_iter`, `Iterator::next`).
+    ForLoop,
+    /// Any other call generated by the compiler (e.g., drops, comparisons generated by
+    /// a `match`, `.await`).
+    Desugaring,
+}
+
+impl CallSourceKind {
+    /// Classify a call terminator using its `CallSource` and the desugaring of its span.
+    pub fn new(call_source: mir::CallSource, span: rustc_span::Span) -> Self {
+        match span.desugaring_kind() {
+            Some(rustc_span::DesugaringKind::QuestionMark) => CallSourceKind::QuestionMark,
+            Some(rustc_span::DesugaringKind::ForLoop) => CallSourceKind::ForLoop,
+            desugaring_kind => match call_source {
+                mir::CallSource::OverloadedOperator => CallSourceKind::Operator,
+                mir::CallSource::MatchCmp | mir::CallSource::Misc => CallSourceKind::Desugaring,
+                mir::CallSource::Normal if desugaring_kind.is_some() => CallSourceKind::Desugaring,
+                mir::CallSource::Normal => CallSourceKind::User,
+            },
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum OperandKind {
     Move,
@@ -40,6 +88,10 @@ pub enum OperandKind {
 pub enum MutabilityKind {
     Mut,
     Not,
+    /// An immutable place whose type (or the type it refers to) contains an `UnsafeCell`
+    /// (e.g., `&RefCell<T>`, `&Mutex<T>`, `Cell<T>` or `&AtomicUsize`),
+    /// so it can be mutated through a shared reference.
+    Interior,
 }
 
 impl From<ty::Mutability> for MutabilityKind {
@@ -81,6 +133,173 @@ impl From<&ty::TyKind<'_>> for RLTyKind {
     }
 }
 
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ClosureKind {
+    Fn,
+    FnMut,
+    FnOnce,
+}
+
+impl From<ty::ClosureKind> for ClosureKind {
+    fn from(closure_kind: ty::ClosureKind) -> Self {
+        match closure_kind {
+            ty::ClosureKind::Fn => ClosureKind::Fn,
+            ty::ClosureKind::FnMut => ClosureKind::FnMut,
+            ty::ClosureKind::FnOnce => ClosureKind::FnOnce,
+        }
+    }
+}
+
+/// How a closure captures a variable from its environment.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CaptureKind {
+    Move,
+    Ref,
+    MutRef,
+}
+
+impl From<ty::UpvarCapture> for CaptureKind {
+    fn from(upvar_capture: ty::UpvarCapture) -> Self {
+        match upvar_capture {
+            ty::UpvarCapture::ByValue => CaptureKind::Move,
+            ty::UpvarCapture::ByRef(ty::BorrowKind::Immutable) => CaptureKind::Ref,
+            // The unique immutable borrow is used when the closure mutates a mutable
+            // referent (e.g., `let y = || *x += 5;` with `x: &mut isize`),
+            // so it is considered as a mutable borrow.
+            ty::UpvarCapture::ByRef(ty::BorrowKind::UniqueImmutable)
+            | ty::UpvarCapture::ByRef(ty::BorrowKind::Mutable) => CaptureKind::MutRef,
+        }
+    }
+}
+
+/// Why a function is an entry point of the call graph, i.e., it can be called from outside
+/// the analyzed crates.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EntryPointKind {
+    /// The `main` function of a binary crate.
+    Main,
+    /// A `#[test]` function.
+    Test,
+    /// A function exported with `#[no_mangle]` or `#[export_name]`, or with a non-Rust ABI
+    /// (e.g., `extern "C"`).
+    Export,
+    /// A function reachable from the root of the crate (e.g., a `pub fn` of a `pub mod`).
+    Public,
+}
+
+/// How a value is passed as argument of a call.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum ArgUsageKind {
+    Move,
+    Copy,
+    Constant,
+    /// The argument is the result of a `clone` (e.g., `f(x.clone())`).
+    Clone,
+    /// The argument is a shared reference to the value.
+    Borrow,
+    /// The argument is a mutable reference to the value.
+    MutBorrow,
+}
+
+/// The concrete type of an argument of a call, without the references,
+/// and how the value is passed.
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+pub struct RLArgTy {
+    ty: String,
+    // The ADT of the type, if the type is an ADT.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        with = "option_def_id_serde"
+    )]
+    adt: Option<DefId>,
+    usage: ArgUsageKind,
+}
+
+// The ADT is left out of the labels of the edges, since its path is already printed.
+impl std::fmt::Debug for RLArgTy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RLArgTy")
+            .field("ty", &self.ty)
+            .field("usage", &self.usage)
+            .finish()
+    }
+}
+
+impl RLArgTy {
+    pub fn new(ty: String, adt: Option<DefId>, usage: ArgUsageKind) -> Self {
+        Self { ty, adt, usage }
+    }
+
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+
+    pub fn adt(&self) -> Option<DefId> {
+        self.adt
+    }
+
+    pub fn usage(&self) -> ArgUsageKind {
+        self.usage
+    }
+}
+
+/// The information about a drop recorded on a `CallKind::Drop` edge.
+/// Whether the drop is on an unwind path or conditional is recorded on the edge,
+/// as for the other calls (see `RLControlFlow`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RLDrop {
+    /// The type of the dropped value.
+    ty: String,
+}
+
+impl RLDrop {
+    pub fn new(ty: String) -> Self {
+        Self { ty }
+    }
+
+    pub fn ty(&self) -> &str {
+        &self.ty
+    }
+}
+
+/// The lines of the definition of a function (or closure), with its body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RLDefSpan {
+    /// The file as printed by the compiler, e.g., `crates/service/src/lib.rs`.
+    file: String,
+    first_line: usize,
+    last_line: usize,
+}
+
+impl RLDefSpan {
+    pub fn new(file: String, first_line: usize, last_line: usize) -> Self {
+        Self {
+            file,
+            first_line,
+            last_line,
+        }
+    }
+
+    pub fn file(&self) -> &str {
+        &self.file
+    }
+
+    pub fn first_line(&self) -> usize {
+        self.first_line
+    }
+
+    pub fn last_line(&self) -> usize {
+        self.last_line
+    }
+}
+
+impl std::fmt::Display for RLDefSpan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}-{}", self.file, self.first_line, self.last_line)
+    }
+}
+
 #[derive(Debug, Clone)]
 /// RlRy is a struct that represents the type of a place (local variable).
 /// It is &'a ty::TyKind<'tcx>,eight the edges of the graph.
@@ -137,6 +356,46 @@ pub enum RLValue<'tcx> {
     TermCallStaticallyUnknown(DefId),
 }
 
+/// The signature of a function, a closure or a statically unknown callee.
+/// The inputs of a closure are untupled, and all the regions are erased.
+#[derive(Debug, Clone)]
+pub struct RLFnSig<'tcx> {
+    pub inputs: Vec<ty::Ty<'tcx>>,
+    pub output: ty::Ty<'tcx>,
+}
+
+impl<'tcx> RLFnSig<'tcx> {
+    pub fn new(inputs: Vec<ty::Ty<'tcx>>, output: ty::Ty<'tcx>) -> Self {
+        Self { inputs, output }
+    }
+
+    /// Two signatures match if they have the same arity and all the types are equal.
+    /// A type which contains a generic parameter matches any type, since we do not know
+    /// how it is instantiated.
+    pub fn matches(&self, other: &RLFnSig<'tcx>) -> bool {
+        let ty_matches =
+            |a: &ty::Ty<'tcx>, b: &ty::Ty<'tcx>| a == b || a.has_param() || b.has_param();
+        self.inputs.len() == other.inputs.len()
+            && self
+                .inputs
+                .iter()
+                .zip(other.inputs.iter())
+                .all(|(a, b)| ty_matches(a, b))
+            && ty_matches(&self.output, &other.output)
+    }
+}
+
+impl std::fmt::Display for RLFnSig<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let inputs = self
+            .inputs
+            .iter()
+            .map(|input| input.to_string())
+            .collect::<Vec<_>>();
+        write!(f, "fn({}) -> {}", inputs.join(", "), self.output)
+    }
+}
+
 pub struct ComeFromSwitchCache<'tcx> {
     pub cache: FxHashMap<mir::Local, Option<RLValue<'tcx>>>,
     pub set_targets: FxHashSet<mir::BasicBlock>,
@@ -215,6 +474,44 @@ where
     /// It is used to retrieve the index of the node in the graph
     /// when we need to add an edge.
     pub rl_graph_index_map: FxHashMap<(DefId, Option<Promoted>), G::Index>,
+
+    /// The body of the function that is visited.
+    pub current_body: Option<&'a mir::Body<'tcx>>,
+
+    /// Map from a statically unknown call site (caller, location) to the index of its node
+    /// in the graph and the signature of the callee.
+    pub map_unknown_call_site: FxIndexMap<(DefId, mir::Location), (G::Index, RLFnSig<'tcx>)>,
+
+    /// Functions and closures whose address is taken (e.g., coerced to a function pointer or
+    /// passed as a value), with their signature.
+    /// They are the candidates of the statically unknown call sites.
+    pub map_address_taken: FxIndexMap<DefId, (CallKind, RLFnSig<'tcx>)>,
+
+    /// The spans of the `unsafe` blocks written by the user in the visited function
+    /// (including the ones of the enclosing function, if it is a closure).
+    pub current_unsafe_blocks: Vec<rustc_span::Span>,
+
+    /// Whether the visited function is (or is a closure defined in) an `unsafe fn`,
+    /// whose body is an unsafe context.
+    pub current_in_unsafe_fn: bool,
+
+    /// Map from a local to the static (or the thread local) it points to.
+    /// For instance, in `_1 = const {alloc1: &u32}` the local `_1` points to
+    /// the static stored in `alloc1`.
+    pub map_static_pointer: FxHashMap<mir::Local, DefId>,
+
+    /// The accesses (`CallKind::Reads` or `CallKind::Writes`) to the statics
+    /// already recorded in the visited function.
+    /// It avoids adding an edge for each access to the same static.
+    pub set_static_accesses: FxHashSet<(DefId, CallKind)>,
+
+    /// The control-flow context (loop depth, cleanup, conditional) of the basic blocks
+    /// of the visited function.
+    pub current_control_flow: Option<RLControlFlow>,
+
+    /// The span of the statement or terminator which is visited,
+    /// recorded on the edges added while visiting it.
+    pub current_span: Option<rustc_span::Span>,
 }
 
 impl<G> RLContext<'_, '_, G>
@@ -237,6 +534,15 @@ where
             map_bb_used_locals: FxHashMap::default(),
             set_function_args: FxHashSet::default(),
             rl_graph_index_map: FxHashMap::default(),
+            current_body: None,
+            map_unknown_call_site: FxIndexMap::default(),
+            map_address_taken: FxIndexMap::default(),
+            current_unsafe_blocks: Vec::new(),
+            current_in_unsafe_fn: false,
+            map_static_pointer: FxHashMap::default(),
+            set_static_accesses: FxHashSet::default(),
+            current_control_flow: None,
+            current_span: None,
         }
     }
 }
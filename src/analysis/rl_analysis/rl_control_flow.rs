@@ -0,0 +1,168 @@
+use rustc_data_structures::graph::{self, dominators};
+use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_index::IndexVec;
+use rustc_middle::mir;
+
+/// `RLControlFlow` contains the control-flow context of the basic blocks of a body:
+/// - the loop depth, i.e., the number of natural loops containing the block;
+/// - whether the block is on a cleanup (unwind) path;
+/// - whether the block is conditionally executed, i.e., there is a path from the entry
+///   to a `return` which does not pass through it (e.g., the block is behind a `SwitchInt`).
+pub struct RLControlFlow {
+    loop_depth: IndexVec<mir::BasicBlock, usize>,
+    cleanup: IndexVec<mir::BasicBlock, bool>,
+    conditional: IndexVec<mir::BasicBlock, bool>,
+}
+
+impl RLControlFlow {
+    pub fn new(body: &mir::Body<'_>) -> Self {
+        Self {
+            loop_depth: Self::compute_loop_depth(body),
+            cleanup: body
+                .basic_blocks
+                .iter()
+                .map(|data| data.is_cleanup)
+                .collect(),
+            conditional: Self::compute_conditional(body),
+        }
+    }
+
+    pub fn loop_depth(&self, bb: mir::BasicBlock) -> usize {
+        self.loop_depth[bb]
+    }
+
+    pub fn is_cleanup(&self, bb: mir::BasicBlock) -> bool {
+        self.cleanup[bb]
+    }
+
+    pub fn is_conditional(&self, bb: mir::BasicBlock) -> bool {
+        self.conditional[bb]
+    }
+
+    /// Compute the natural loops of the body from its back-edges, i.e., the edges
+    /// `latch -> header` where the header dominates the latch.
+    /// The natural loop of a back-edge contains the header and all the blocks
+    /// which reach the latch without passing through the header.
+    /// The loops with the same header are merged.
+    fn compute_loop_depth(body: &mir::Body<'_>) -> IndexVec<mir::BasicBlock, usize> {
+        let dominators = body.basic_blocks.dominators();
+        let predecessors = body.basic_blocks.predecessors();
+
+        let mut loops: FxHashMap<mir::BasicBlock, FxHashSet<mir::BasicBlock>> =
+            FxHashMap::default();
+        for (latch, data) in body.basic_blocks.iter_enumerated() {
+            if !dominators.is_reachable(latch) {
+                continue;
+            }
+            for header in data.terminator().successors() {
+                if !dominators.dominates(header, latch) {
+                    continue;
+                }
+                let natural_loop = loops.entry(header).or_default();
+                natural_loop.insert(header);
+                let mut worklist = vec![latch];
+                while let Some(bb) = worklist.pop() {
+                    if natural_loop.insert(bb) {
+                        worklist.extend(predecessors[bb].iter().copied());
+                    }
+                }
+            }
+        }
+
+        let mut loop_depth = IndexVec::from_elem(0, &body.basic_blocks);
+        for natural_loop in loops.values() {
+            for bb in natural_loop {
+                loop_depth[*bb] += 1;
+            }
+        }
+        loop_depth
+    }
+
+    /// A block is unconditional if it post-dominates the entry, i.e., every path from
+    /// the entry to a `return` passes through it.
+    /// The post-dominators are the dominators of the reversed control-flow graph
+    /// (without the cleanup blocks), whose start node is a virtual exit linked to
+    /// all the `return` blocks.
+    ///
+    /// The cleanup blocks are always conditional, since they are executed only on unwind.
+    ///
+    /// If the entry never reaches a `return` (e.g., the function always panics or loops
+    /// forever), no block is conditional.
+    fn compute_conditional(body: &mir::Body<'_>) -> IndexVec<mir::BasicBlock, bool> {
+        let reversed_cfg = ReversedCfg::new(body);
+        let post_dominators = dominators::dominators(&reversed_cfg);
+        let entry = mir::START_BLOCK.as_usize();
+
+        body.basic_blocks
+            .indices()
+            .map(|bb| {
+                post_dominators.is_reachable(entry)
+                    && !(post_dominators.is_reachable(bb.as_usize())
+                        && post_dominators.dominates(bb.as_usize(), entry))
+            })
+            .collect()
+    }
+}
+
+/// The reversed control-flow graph of a body, without the cleanup blocks.
+/// The node `i` is the basic block `i`, and the last node is a virtual exit
+/// which is the start node and has an edge to each `return` block.
+struct ReversedCfg {
+    successors: Vec<Vec<usize>>,
+    predecessors: Vec<Vec<usize>>,
+}
+
+impl ReversedCfg {
+    fn new(body: &mir::Body<'_>) -> Self {
+        let exit = body.basic_blocks.len();
+        let mut successors = vec![Vec::new(); exit + 1];
+        let mut predecessors = vec![Vec::new(); exit + 1];
+        for (bb, data) in body.basic_blocks.iter_enumerated() {
+            if data.is_cleanup {
+                continue;
+            }
+            if let mir::TerminatorKind::Return = data.terminator().kind {
+                successors[exit].push(bb.as_usize());
+                predecessors[bb.as_usize()].push(exit);
+            }
+            for target in data.terminator().successors() {
+                if body.basic_blocks[target].is_cleanup {
+                    continue;
+                }
+                // The edge `bb -> target` is reversed.
+                successors[target.as_usize()].push(bb.as_usize());
+                predecessors[bb.as_usize()].push(target.as_usize());
+            }
+        }
+        Self {
+            successors,
+            predecessors,
+        }
+    }
+}
+
+impl graph::DirectedGraph for ReversedCfg {
+    type Node = usize;
+
+    fn num_nodes(&self) -> usize {
+        self.successors.len()
+    }
+}
+
+impl graph::StartNode for ReversedCfg {
+    fn start_node(&self) -> usize {
+        self.successors.len() - 1
+    }
+}
+
+impl graph::Successors for ReversedCfg {
+    fn successors(&self, node: usize) -> impl Iterator<Item = usize> {
+        self.successors[node].iter().copied()
+    }
+}
+
+impl graph::Predecessors for ReversedCfg {
+    fn predecessors(&self, node: usize) -> impl Iterator<Item = usize> {
+        self.predecessors[node].iter().copied()
+    }
+}
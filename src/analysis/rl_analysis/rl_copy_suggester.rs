@@ -0,0 +1,124 @@
+use rustc_data_structures::fx::FxIndexMap;
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::{self, TyCtxt};
+
+use super::rl_graph::{RLGraph, RLGraphNode, RLIndex, RLNode};
+
+/// A local ADT which is cloned at many call sites and which could derive `Copy`.
+#[derive(Debug, Clone)]
+pub struct RLCopySuggestion {
+    ty: String,
+    size: u64,
+    /// The functions cloning a value of the type, with the number of clones in each one.
+    clone_sites: Vec<(String, usize)>,
+}
+
+impl RLCopySuggestion {
+    pub fn clones(&self) -> usize {
+        self.clone_sites.iter().map(|(_, count)| count).sum()
+    }
+}
+
+impl std::fmt::Display for RLCopySuggestion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}` ({} bytes) is cloned at {} call sites, consider deriving `Copy`",
+            self.ty,
+            self.size,
+            self.clones()
+        )?;
+        for (function, count) in &self.clone_sites {
+            write!(f, "\n    {} ({})", function, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// `RLCopySuggester` finds the structs and enums of the crate which are not `Copy`
+/// but could derive it, i.e., all their fields are `Copy` and they do not implement `Drop`,
+/// whose size is at most `max_size` bytes, and which are cloned at `min_clones` or more
+/// call sites.
+///
+/// The generic types are skipped, since their layout depends on the generic arguments.
+pub struct RLCopySuggester<'tcx> {
+    tcx: TyCtxt<'tcx>,
+    max_size: u64,
+    min_clones: usize,
+}
+
+impl<'tcx> RLCopySuggester<'tcx> {
+    pub fn new(tcx: TyCtxt<'tcx>, max_size: u64, min_clones: usize) -> Self {
+        Self {
+            tcx,
+            max_size,
+            min_clones,
+        }
+    }
+
+    pub fn suggestions<G>(&self, rl_graph: &G) -> Vec<RLCopySuggestion>
+    where
+        G: RLGraph<Node = RLNode, Index = RLIndex>,
+    {
+        // The functions cloning a value of each type, with the number of clones in each one.
+        let mut clone_sites: FxIndexMap<&str, FxIndexMap<RLIndex, usize>> = FxIndexMap::default();
+        for (index, node) in rl_graph.rl_nodes() {
+            for ty in node.clones() {
+                *clone_sites.entry(ty).or_default().entry(index).or_default() += 1;
+            }
+        }
+        let typing_env = ty::TypingEnv::fully_monomorphized();
+
+        let mut suggestions = Vec::new();
+        for local_def_id in self.tcx.hir_crate_items(()).definitions() {
+            let def_id = local_def_id.to_def_id();
+            if !matches!(self.tcx.def_kind(def_id), DefKind::Struct | DefKind::Enum)
+                || self
+                    .tcx
+                    .generics_of(def_id)
+                    .requires_monomorphization(self.tcx)
+            {
+                continue;
+            }
+            // The lifetime parameters are erased.
+            let adt_ty = self
+                .tcx
+                .erase_regions(self.tcx.type_of(def_id).instantiate_identity());
+            let ty::TyKind::Adt(adt_def, args) = adt_ty.kind() else {
+                unreachable!()
+            };
+            if self.tcx.type_is_copy_modulo_regions(typing_env, adt_ty)
+                || adt_def.has_dtor(self.tcx)
+                || !adt_def.all_fields().all(|field| {
+                    self.tcx
+                        .type_is_copy_modulo_regions(typing_env, field.ty(self.tcx, args))
+                })
+            {
+                continue;
+            }
+            let Ok(layout) = self.tcx.layout_of(typing_env.as_query_input(adt_ty)) else {
+                continue;
+            };
+            if layout.size.bytes() > self.max_size {
+                continue;
+            }
+
+            let ty = adt_ty.to_string();
+            let Some(functions) = clone_sites.get(ty.as_str()) else {
+                continue;
+            };
+            let suggestion = RLCopySuggestion {
+                ty,
+                size: layout.size.bytes(),
+                clone_sites: functions
+                    .iter()
+                    .map(|(function, count)| (rl_graph.rl_node(*function).def_id_str(), *count))
+                    .collect(),
+            };
+            if suggestion.clones() >= self.min_clones {
+                suggestions.push(suggestion);
+            }
+        }
+        suggestions
+    }
+}
@@ -0,0 +1,83 @@
+use rustc_data_structures::fx::FxIndexMap;
+use std::collections::BTreeMap;
+
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use super::rl_petgraph::call_graph;
+
+/// A function of the dominator tree of an entry point, with its depth in the tree and the
+/// number of functions it dominates, i.e., the functions which are reached from the entry
+/// point only through it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct RLDominatorTreeNode {
+    node: RLIndex,
+    depth: usize,
+    dominated: usize,
+}
+
+impl RLDominatorTreeNode {
+    /// The dominator tree of the functions reachable from `root`, in preorder.
+    pub fn collect<G>(rl_graph: &G, root: RLIndex) -> Vec<Self>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let call_graph = call_graph(rl_graph);
+        let Some(root) = call_graph
+            .node_indices()
+            .find(|node| call_graph[*node] == root)
+        else {
+            return Vec::new();
+        };
+        let dominators = petgraph::algo::dominators::simple_fast(&call_graph, root);
+
+        // The children of each function in the tree, sorted by index.
+        let mut children: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for node in call_graph.node_indices() {
+            if let Some(dominator) = dominators.immediate_dominator(node) {
+                children.entry(dominator).or_default().push(node);
+            }
+        }
+
+        let mut preorder = Vec::new();
+        let mut stack = vec![(root, 0)];
+        while let Some((node, depth)) = stack.pop() {
+            preorder.push((node, depth));
+            if let Some(children) = children.get(&node) {
+                stack.extend(children.iter().rev().map(|child| (*child, depth + 1)));
+            }
+        }
+
+        // In the reversed preorder, the children of a function precede it.
+        let mut dominated = FxIndexMap::default();
+        for (node, _) in preorder.iter().rev() {
+            let count = children.get(node).map_or(0, |children| {
+                children
+                    .iter()
+                    .map(|child| 1 + dominated[child])
+                    .sum::<usize>()
+            });
+            dominated.insert(*node, count);
+        }
+
+        preorder
+            .into_iter()
+            .map(|(node, depth)| Self {
+                node: call_graph[node],
+                depth,
+                dominated: dominated[&node],
+            })
+            .collect()
+    }
+
+    pub fn node(&self) -> RLIndex {
+        self.node
+    }
+
+    /// The depth in the tree, where the entry point is at depth 0.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    pub fn dominated(&self) -> usize {
+        self.dominated
+    }
+}
@@ -0,0 +1,70 @@
+use rustc_abi::ExternAbi;
+use rustc_hash::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_middle::{
+    mir,
+    ty::{self, TyCtxt},
+};
+use rustc_span::{def_id::LocalDefId, sym};
+
+use super::rl_context::EntryPointKind;
+
+/// Collect the `main`, `#[test]` and exported functions of the crate.
+/// The public functions are not collected, since they are already marked as public
+/// in the graph.
+///
+/// A `#[test]` function is not called by the crate: the test harness generates a `const`
+/// (marked with `#[rustc_test_marker]`) with a closure calling it, so the test functions
+/// are the local callees of these closures.
+pub fn collect_entry_points(tcx: TyCtxt<'_>) -> FxHashMap<LocalDefId, EntryPointKind> {
+    let mut entry_points = FxHashMap::default();
+
+    for local_def_id in tcx.hir_body_owners() {
+        let def_id = local_def_id.to_def_id();
+        match tcx.def_kind(def_id) {
+            DefKind::Fn | DefKind::AssocFn => {
+                let is_export = tcx.codegen_fn_attrs(def_id).contains_extern_indicator()
+                    || tcx.fn_sig(def_id).skip_binder().abi() != ExternAbi::Rust;
+                if is_export {
+                    entry_points.insert(local_def_id, EntryPointKind::Export);
+                }
+            }
+            DefKind::Closure
+                if tcx.has_attr(tcx.typeck_root_def_id(def_id), sym::rustc_test_marker) =>
+            {
+                let body = tcx.instance_mir(ty::InstanceKind::Item(def_id));
+                for data in body.basic_blocks.iter() {
+                    let mir::TerminatorKind::Call { func, .. } = &data.terminator().kind else {
+                        continue;
+                    };
+                    if let Some((callee, _)) = func.const_fn_def() {
+                        if let Some(callee) = callee.as_local() {
+                            entry_points.insert(callee, EntryPointKind::Test);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if let Some((main_def_id, _)) = tcx.entry_fn(()) {
+        if let Some(main_local_def_id) = main_def_id.as_local() {
+            entry_points.insert(main_local_def_id, EntryPointKind::Main);
+        }
+    }
+
+    entry_points
+}
+
+/// Whether the function can be reported as unreachable, i.e., it is a function or
+/// an inherent method (the methods of the traits are called through their trait).
+pub fn is_local_fn(tcx: TyCtxt<'_>, local_def_id: LocalDefId) -> bool {
+    match tcx.def_kind(local_def_id) {
+        DefKind::Fn => true,
+        DefKind::AssocFn => tcx
+            .impl_of_method(local_def_id.to_def_id())
+            .is_some_and(|impl_def_id| tcx.trait_id_of_impl(impl_def_id).is_none()),
+        _ => false,
+    }
+}
@@ -1,7 +1,12 @@
-use crate::analysis::utils::{DUMMY_CRATE_NUM, DUMMY_DEF_INDEX};
-
-use super::rl_context::{CallKind, MutabilityKind, OperandKind, RLTyKind};
-use rustc_hir::def_id::{CrateNum, DefIndex};
+use super::rl_borrow_suggester::RLReadOnlyParam;
+use super::rl_borrowck::RLBorrowckFacts;
+use super::rl_condensed::RLCondensedGraph;
+use super::rl_context::{
+    CallKind, CallSourceKind, CaptureKind, ClosureKind, EntryPointKind, MutabilityKind,
+    OperandKind, RLArgTy, RLDefSpan, RLDrop, RLTyKind,
+};
+use super::rl_ownership::RLOwnershipFlow;
+use super::rl_ownership_cost::RLOwnershipCost;
 use rustc_middle::mir::Promoted;
 use rustc_span::def_id::DefId;
 use serde::{Deserialize, Serialize};
@@ -35,15 +40,344 @@ pub trait RLGraph {
 
     fn rl_add_node(&mut self, node: Self::Node) -> Self::Index;
     fn rl_add_edge(&mut self, source: Self::Index, target: Self::Index, edge: Self::Edge);
+    fn rl_node(&self, index: Self::Index) -> &Self::Node;
+    fn rl_node_mut(&mut self, index: Self::Index) -> &mut Self::Node;
+    /// Return the safe public functions which transitively reach unsafe code
+    /// (an `unsafe fn`, an `unsafe` block, or a call in an unsafe context), each one
+    /// with the functions containing the unsafe code it reaches.
+    fn unsafe_surface(&self) -> Vec<(Self::Index, Vec<Self::Index>)>;
+    /// Return the entry points of the graph (see `RLNode::entry_point`).
+    fn entry_points(&self, include_public: bool) -> Vec<(Self::Index, EntryPointKind)>;
+    /// Return the local functions (see `RLNode::is_local_fn`) which are not reachable
+    /// from any entry point.
+    fn unreachable(&self, include_public: bool) -> Vec<Self::Index>;
+    /// Return the functions which transitively call any of the `nodes`, including them,
+    /// sorted by index.
+    fn callers(&self, nodes: &[Self::Index]) -> Vec<Self::Index>;
+    /// Return all the nodes `(index, node)` of the graph.
+    fn rl_nodes(&self) -> Vec<(Self::Index, &Self::Node)>;
+    /// Return all the edges `(source, target, edge)` of the graph.
+    fn rl_edges(&self) -> Vec<(Self::Index, Self::Index, &Self::Edge)>;
+    /// Return the graph whose nodes are the groups of the nodes given by `group_of`,
+    /// with an edge between two groups aggregating the edges between their nodes.
+    fn condense(&self, group_of: &dyn Fn(&Self::Node) -> String) -> RLCondensedGraph;
     fn merge(&mut self, other: &Self);
     fn as_dot_str(&self) -> String;
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RLNode {
+    #[serde(with = "def_id_serde")]
     def_id: DefId,
+    #[serde(with = "promoted_serde")]
     promoted: Option<Promoted>,
     def_id_str: String,
+    // The path of the function with its crate (see `rl_condensed::def_path`), e.g.,
+    // `temp::parser::parse`. It is `None` for a statically unknown call site.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    def_path: Option<String>,
+    // The name of the crate defining the function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    crate_name: Option<String>,
+    // The path of the type of the `impl` of the function (see `rl_condensed::impl_path`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    impl_path: Option<String>,
+    // The path of the module of the function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    module_path: Option<String>,
+    // It is `Some` only when the node represents a statically unknown call site.
+    // It contains the signature of the callee, e.g. `fn(T) -> ()`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    unknown_fn_sig: Option<String>,
+    // It is `Some` only when the node represents a closure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    closure_kind: Option<ClosureKind>,
+    // The variables (or places) captured by the closure and how they are captured.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    closure_captures: Vec<(String, CaptureKind)>,
+    // It is `true` only when the node represents an `unsafe fn`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    unsafe_fn: bool,
+    // It is `true` only when the node represents a function reachable from outside its crate.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    public: bool,
+    // The number of `unsafe` blocks written by the user in the body of the function.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    unsafe_blocks: usize,
+    // The number of calls which borrow a value at runtime in the body of the function,
+    // in shared mode (e.g., `RefCell::borrow`, `RwLock::read`) and in exclusive mode
+    // (e.g., `RefCell::borrow_mut`, `Mutex::lock`).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    runtime_borrows: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    runtime_borrows_mut: usize,
+    // The type of the value cloned at each call to `Clone::clone` in the body of the function.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    clones: Vec<String>,
+    // The ADT of the value cloned at each call to `Clone::clone` whose value is an ADT.
+    #[serde(default, skip_serializing_if = "Vec::is_empty", with = "def_ids_serde")]
+    cloned_adts: Vec<DefId>,
+    // The ownership metrics computed by the borrow checker, only with `--borrowck-facts`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    borrowck_facts: Option<RLBorrowckFacts>,
+    // The ownership facts of the body of the function, only with `--print-ownership-chains`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ownership_flow: Option<RLOwnershipFlow>,
+    // The by-value parameters which are only read, only with `--suggest-borrows`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    read_only_params: Vec<RLReadOnlyParam>,
+    // It is `Some` only when the node represents a `main`, `#[test]` or exported function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    entry_point: Option<EntryPointKind>,
+    // It is `true` only when the node represents a function or an inherent method defined
+    // in the analyzed crate, i.e., one which can be reported as unreachable.
+    // The methods of the traits are never reported, since they are called through the trait.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    local_fn: bool,
+    // The ownership cost of the function, only with `--transitive-cost`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ownership_cost: Option<RLOwnershipCost>,
+    // The lines of the definition, known only by the crate defining the function.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    def_span: Option<RLDefSpan>,
+}
+
+fn is_zero(value: &usize) -> bool {
+    *value == 0
+}
+
+impl RLNode {
+    /// Create a node representing a statically unknown call site.
+    ///
+    /// The `def_id` is the one of the function containing the call site, and the
+    /// `call_site` is a string which uniquely identifies the call site in the crate
+    /// (e.g., `temp::main@bb0[3]`).
+    pub fn create_statically_unknown(def_id: DefId, call_site: String, fn_sig: String) -> Self {
+        Self {
+            def_id,
+            promoted: None,
+            def_id_str: format!("STATICALLY_UNKNOWN ~ {}: {}", call_site, fn_sig),
+            def_path: None,
+            crate_name: None,
+            impl_path: None,
+            module_path: None,
+            unknown_fn_sig: Some(fn_sig),
+            closure_kind: None,
+            closure_captures: Vec::new(),
+            unsafe_fn: false,
+            public: false,
+            unsafe_blocks: 0,
+            runtime_borrows: 0,
+            runtime_borrows_mut: 0,
+            clones: Vec::new(),
+            cloned_adts: Vec::new(),
+            borrowck_facts: None,
+            ownership_flow: None,
+            read_only_params: Vec::new(),
+            entry_point: None,
+            local_fn: false,
+            ownership_cost: None,
+            def_span: None,
+        }
+    }
+
+    /// Set the kind of the closure and what it captures from its environment.
+    pub fn set_closure_info(
+        &mut self,
+        closure_kind: ClosureKind,
+        closure_captures: Vec<(String, CaptureKind)>,
+    ) {
+        self.closure_kind = Some(closure_kind);
+        self.closure_captures = closure_captures;
+    }
+
+    pub fn closure_kind(&self) -> Option<ClosureKind> {
+        self.closure_kind
+    }
+
+    pub fn closure_captures(&self) -> &[(String, CaptureKind)] {
+        &self.closure_captures
+    }
+
+    pub fn set_unsafe_fn(&mut self, unsafe_fn: bool) {
+        self.unsafe_fn = unsafe_fn;
+    }
+
+    pub fn is_unsafe_fn(&self) -> bool {
+        self.unsafe_fn
+    }
+
+    pub fn set_unsafe_blocks(&mut self, unsafe_blocks: usize) {
+        self.unsafe_blocks = unsafe_blocks;
+    }
+
+    pub fn unsafe_blocks(&self) -> usize {
+        self.unsafe_blocks
+    }
+
+    /// Count a call which borrows a value at runtime, in exclusive mode if `mutability`
+    /// is `MutabilityKind::Mut`.
+    pub fn add_runtime_borrow(&mut self, mutability: MutabilityKind) {
+        match mutability {
+            MutabilityKind::Mut => self.runtime_borrows_mut += 1,
+            MutabilityKind::Not | MutabilityKind::Interior => self.runtime_borrows += 1,
+        }
+    }
+
+    pub fn runtime_borrows(&self) -> usize {
+        self.runtime_borrows
+    }
+
+    pub fn runtime_borrows_mut(&self) -> usize {
+        self.runtime_borrows_mut
+    }
+
+    /// Record a call to `Clone::clone` on a value of type `ty`, whose ADT is `adt` (if any).
+    pub fn add_clone(&mut self, ty: String, adt: Option<DefId>) {
+        self.clones.push(ty);
+        self.cloned_adts.extend(adt);
+    }
+
+    pub fn clones(&self) -> &[String] {
+        &self.clones
+    }
+
+    pub fn cloned_adts(&self) -> &[DefId] {
+        &self.cloned_adts
+    }
+
+    pub fn set_borrowck_facts(&mut self, borrowck_facts: RLBorrowckFacts) {
+        self.borrowck_facts = Some(borrowck_facts);
+    }
+
+    pub fn borrowck_facts(&self) -> Option<&RLBorrowckFacts> {
+        self.borrowck_facts.as_ref()
+    }
+
+    pub fn set_ownership_flow(&mut self, ownership_flow: RLOwnershipFlow) {
+        self.ownership_flow = Some(ownership_flow);
+    }
+
+    pub fn ownership_flow(&self) -> Option<&RLOwnershipFlow> {
+        self.ownership_flow.as_ref()
+    }
+
+    pub fn set_read_only_params(&mut self, read_only_params: Vec<RLReadOnlyParam>) {
+        self.read_only_params = read_only_params;
+    }
+
+    pub fn read_only_params(&self) -> &[RLReadOnlyParam] {
+        &self.read_only_params
+    }
+
+    pub fn set_public(&mut self, public: bool) {
+        self.public = public;
+    }
+
+    pub fn is_public(&self) -> bool {
+        self.public
+    }
+
+    pub fn set_entry_point(&mut self, entry_point: EntryPointKind) {
+        self.entry_point = Some(entry_point);
+    }
+
+    /// The kind of entry point of the function, where the public functions are entry points
+    /// only if `include_public` is `true`.
+    pub fn entry_point(&self, include_public: bool) -> Option<EntryPointKind> {
+        match self.entry_point {
+            Some(entry_point) => Some(entry_point),
+            None if include_public && self.public => Some(EntryPointKind::Public),
+            None => None,
+        }
+    }
+
+    pub fn set_local_fn(&mut self, local_fn: bool) {
+        self.local_fn = local_fn;
+    }
+
+    pub fn is_local_fn(&self) -> bool {
+        self.local_fn
+    }
+
+    pub fn set_ownership_cost(&mut self, ownership_cost: RLOwnershipCost) {
+        self.ownership_cost = Some(ownership_cost);
+    }
+
+    pub fn ownership_cost(&self) -> Option<RLOwnershipCost> {
+        self.ownership_cost
+    }
+
+    pub fn set_def_span(&mut self, def_span: RLDefSpan) {
+        self.def_span = Some(def_span);
+    }
+
+    pub fn def_span(&self) -> Option<&RLDefSpan> {
+        self.def_span.as_ref()
+    }
+
+    /// Merge the information about the function known only by the crate defining it
+    /// (e.g., whether it is public) from the node of `other`, which represents the same
+    /// function in the graph of another crate.
+    pub fn merge(&mut self, other: &Self) {
+        self.public |= other.public;
+        self.local_fn |= other.local_fn;
+        if self.entry_point.is_none() {
+            self.entry_point = other.entry_point;
+        }
+        if self.def_span.is_none() {
+            self.def_span = other.def_span.clone();
+        }
+        if self.ownership_flow.is_none() {
+            self.ownership_flow = other.ownership_flow.clone();
+        }
+    }
+
+    pub fn def_id(&self) -> DefId {
+        self.def_id
+    }
+
+    pub fn set_def_path(&mut self, crate_name: String, def_path: String) {
+        self.crate_name = Some(crate_name);
+        self.def_path = Some(def_path);
+    }
+
+    pub fn set_group_paths(&mut self, impl_path: String, module_path: String) {
+        self.impl_path = Some(impl_path);
+        self.module_path = Some(module_path);
+    }
+
+    /// The path of the function with its crate, e.g., `temp::parser::parse`.
+    /// It is `None` for a statically unknown call site.
+    pub fn def_path(&self) -> Option<&str> {
+        self.def_path.as_deref()
+    }
+
+    /// The name of the crate defining the function.
+    /// It is `None` for a statically unknown call site.
+    pub fn crate_name(&self) -> Option<&str> {
+        self.crate_name.as_deref()
+    }
+
+    /// The path of the type of the `impl` of the function, or the path of the function
+    /// outside the `impl`s. It is `None` for a statically unknown call site.
+    pub fn impl_path(&self) -> Option<&str> {
+        self.impl_path.as_deref()
+    }
+
+    /// The path of the module of the function, e.g., `temp::parser`.
+    /// It is `None` for a statically unknown call site.
+    pub fn module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+
+    pub fn is_statically_unknown(&self) -> bool {
+        self.unknown_fn_sig.is_some()
+    }
+
+    pub fn unknown_fn_sig(&self) -> Option<&str> {
+        self.unknown_fn_sig.as_deref()
+    }
 }
 
 impl PartialEq for RLNode {
@@ -74,17 +408,31 @@ impl PartialEq for RLNode {
 
 impl RLGraphNode for RLNode {
     fn create(def_id: DefId, promoted: Option<Promoted>) -> Self {
-        let def_id_str = match def_id {
-            DefId {
-                krate: DUMMY_CRATE_NUM,
-                index: DUMMY_DEF_INDEX,
-            } => "STATICALLY_UNKNOWN".to_string(),
-            _ => format!("{:?}", def_id),
-        };
         Self {
             def_id,
             promoted,
-            def_id_str,
+            def_id_str: format!("{:?}", def_id),
+            def_path: None,
+            crate_name: None,
+            impl_path: None,
+            module_path: None,
+            unknown_fn_sig: None,
+            closure_kind: None,
+            closure_captures: Vec::new(),
+            unsafe_fn: false,
+            public: false,
+            unsafe_blocks: 0,
+            runtime_borrows: 0,
+            runtime_borrows_mut: 0,
+            clones: Vec::new(),
+            cloned_adts: Vec::new(),
+            borrowck_facts: None,
+            ownership_flow: None,
+            read_only_params: Vec::new(),
+            entry_point: None,
+            local_fn: false,
+            ownership_cost: None,
+            def_span: None,
         }
     }
 
@@ -97,60 +445,109 @@ impl RLGraphNode for RLNode {
     }
 }
 
-impl Serialize for RLNode {
-    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+// The `DefId` is serialized as `krate:index`.
+mod def_id_serde {
+    use rustc_hir::def_id::{CrateNum, DefIndex};
+    use rustc_span::def_id::DefId;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(def_id: &DefId, serializer: S) -> Result<S::Ok, S::Error>
     where
-        S: serde::Serializer,
+        S: Serializer,
     {
-        let promoted = match self.promoted {
-            Some(promoted) => promoted.as_u32(),
-            None => u32::MAX,
-        };
-        let binding = self.def_id.index.as_u32();
-        let index = match self.def_id.index {
-            DUMMY_DEF_INDEX => "STATICALLY_UNKNOWN",
-            _ => &binding.to_string(),
-        };
-        serializer.serialize_str(&format!(
-            "{}:{}:{}:{}",
-            self.def_id.krate.as_u32(),
-            index,
-            promoted,
-            self.def_id_str,
-        ))
+        serializer.serialize_str(&to_string(def_id))
     }
-}
 
-impl<'de> Deserialize<'de> for RLNode {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<DefId, D::Error>
     where
-        D: serde::Deserializer<'de>,
+        D: Deserializer<'de>,
     {
-        let s = String::deserialize(deserializer)?;
-        let parts: Vec<&str> = s.split(':').collect();
-        let krate = parts[0].parse().unwrap();
-        let index = match parts[1] {
-            "STATICALLY_UNKNOWN" => DUMMY_DEF_INDEX,
-            _ => DefIndex::from_u32(parts[1].parse().unwrap()),
-        };
-        let promoted = match parts[2] {
-            "4294967295" /* u32::MAX */ => None,
-            _ => Some(Promoted::from_u32(parts[2].parse().unwrap())),
-        };
-        // We need to join the rest of the parts because the def_id_str can contain ':' characters.
-        // And remove the last character because it is a trailing '"' character.
-        let def_id_str = parts[3..].join(":");
-        Ok(Self {
-            def_id: DefId {
-                krate: CrateNum::from_u32(krate),
-                index,
-            },
-            promoted,
-            def_id_str,
+        from_str(&String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+    }
+
+    pub(super) fn to_string(def_id: &DefId) -> String {
+        format!("{}:{}", def_id.krate.as_u32(), def_id.index.as_u32())
+    }
+
+    pub(super) fn from_str(s: &str) -> Result<DefId, String> {
+        let (krate, index) = s
+            .split_once(':')
+            .ok_or_else(|| format!("Invalid DefId: {}", s))?;
+        Ok(DefId {
+            krate: CrateNum::from_u32(krate.parse().map_err(|error| format!("{}", error))?),
+            index: DefIndex::from_u32(index.parse().map_err(|error| format!("{}", error))?),
         })
     }
 }
 
+pub(super) mod option_def_id_serde {
+    use rustc_span::def_id::DefId;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(def_id: &Option<DefId>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match def_id {
+            Some(def_id) => serializer.serialize_some(&super::def_id_serde::to_string(def_id)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DefId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Option::<String>::deserialize(deserializer)?
+            .map(|s| super::def_id_serde::from_str(&s).map_err(serde::de::Error::custom))
+            .transpose()
+    }
+}
+
+mod def_ids_serde {
+    use rustc_span::def_id::DefId;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(def_ids: &[DefId], serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_seq(def_ids.iter().map(super::def_id_serde::to_string))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<DefId>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|s| super::def_id_serde::from_str(s).map_err(serde::de::Error::custom))
+            .collect()
+    }
+}
+
+mod promoted_serde {
+    use rustc_middle::mir::Promoted;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(promoted: &Option<Promoted>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_u32(promoted.map_or(u32::MAX, |promoted| promoted.as_u32()))
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Promoted>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        match u32::deserialize(deserializer)? {
+            u32::MAX => Ok(None),
+            promoted => Ok(Some(Promoted::from_u32(promoted))),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RLEdge {
     // It represents the kind of the call and the multiplier of the call.
@@ -159,6 +556,140 @@ pub struct RLEdge {
     call_multiplier: CallKind,
     // It represents the kind of the arguments.
     arg_weights: Vec<(OperandKind, MutabilityKind, RLTyKind)>,
+    // It is `Some` only for `CallKind::Indirect` edges.
+    // It contains the path of the non-local function which receives the closure.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    via: Option<String>,
+    // It is `Some` only for `CallKind::Drop` edges.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    drop: Option<RLDrop>,
+    // It represents where the call comes from in the source code
+    // (e.g., a call written by the user or the desugaring of a `for` loop).
+    #[serde(default)]
+    call_source: CallSourceKind,
+    // It is `true` when the call happens in an unsafe context,
+    // i.e., inside an `unsafe` block or in the body of an `unsafe fn`.
+    #[serde(default)]
+    unsafe_context: bool,
+    // The number of loops containing the call site.
+    #[serde(default)]
+    loop_depth: usize,
+    // It is `true` when the call site is on a cleanup (unwind) path.
+    #[serde(default)]
+    cleanup: bool,
+    // It is `true` when the call site is not executed on every path from the entry
+    // of the caller to a `return` (e.g., it is behind a `SwitchInt`).
+    #[serde(default)]
+    conditional: bool,
+    // The concrete type of each argument and how it is passed.
+    // It is empty for the `Indirect`, `Drop` and `OverApproximated` edges.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    arg_tys: Vec<RLArgTy>,
+    // The span of the call site (or of the access to the static), e.g.,
+    // `src/lib.rs:10:5: 10:18`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    span: Option<String>,
+}
+
+impl RLEdge {
+    pub fn call_kind(&self) -> &CallKind {
+        &self.call_multiplier
+    }
+
+    pub fn arg_weights(&self) -> &[(OperandKind, MutabilityKind, RLTyKind)] {
+        &self.arg_weights
+    }
+
+    pub fn via(&self) -> Option<&str> {
+        self.via.as_deref()
+    }
+
+    pub fn set_via(&mut self, via: String) {
+        self.via = Some(via);
+    }
+
+    pub fn drop(&self) -> Option<&RLDrop> {
+        self.drop.as_ref()
+    }
+
+    pub fn set_drop(&mut self, drop: RLDrop) {
+        self.drop = Some(drop);
+    }
+
+    pub fn call_source(&self) -> CallSourceKind {
+        self.call_source
+    }
+
+    pub fn set_call_source(&mut self, call_source: CallSourceKind) {
+        self.call_source = call_source;
+    }
+
+    pub fn unsafe_context(&self) -> bool {
+        self.unsafe_context
+    }
+
+    pub fn set_unsafe_context(&mut self, unsafe_context: bool) {
+        self.unsafe_context = unsafe_context;
+    }
+
+    pub fn loop_depth(&self) -> usize {
+        self.loop_depth
+    }
+
+    pub fn is_cleanup(&self) -> bool {
+        self.cleanup
+    }
+
+    pub fn is_conditional(&self) -> bool {
+        self.conditional
+    }
+
+    pub fn arg_tys(&self) -> &[RLArgTy] {
+        &self.arg_tys
+    }
+
+    pub fn set_arg_tys(&mut self, arg_tys: Vec<RLArgTy>) {
+        self.arg_tys = arg_tys;
+    }
+
+    pub fn span(&self) -> Option<&str> {
+        self.span.as_deref()
+    }
+
+    pub fn set_span(&mut self, span: String) {
+        self.span = Some(span);
+    }
+
+    /// The ownership score of the edge: 1 for the call, plus the weight of each argument.
+    /// An argument weighs 2 if it is moved, 1 if it is copied and 0 if it is a constant,
+    /// doubled if it is mutable (or interior mutable) and doubled if its type is composite
+    /// or a closure.
+    pub fn ownership_score(&self) -> usize {
+        let arg_weight = |(operand, mutability, ty): &(OperandKind, MutabilityKind, RLTyKind)| {
+            let operand_weight = match operand {
+                OperandKind::Move => 2,
+                OperandKind::Copy => 1,
+                OperandKind::Constant => 0,
+            };
+            let mutability_weight = match mutability {
+                MutabilityKind::Mut | MutabilityKind::Interior => 2,
+                MutabilityKind::Not => 1,
+            };
+            let ty_weight = match ty {
+                RLTyKind::Composite | RLTyKind::Closure => 2,
+                RLTyKind::Primitive | RLTyKind::Unknown => 1,
+            };
+            operand_weight * mutability_weight * ty_weight
+        };
+        1 + self.arg_weights.iter().map(arg_weight).sum::<usize>()
+    }
+
+    /// Set the control-flow context of the call site.
+    pub fn set_control_flow(&mut self, loop_depth: usize, cleanup: bool, conditional: bool) {
+        self.loop_depth = loop_depth;
+        self.cleanup = cleanup;
+        self.conditional = conditional;
+    }
 }
 
 impl RLGraphEdge for RLEdge {
@@ -167,6 +698,15 @@ impl RLGraphEdge for RLEdge {
         Self {
             call_multiplier,
             arg_weights,
+            via: None,
+            drop: None,
+            call_source: CallSourceKind::User,
+            unsafe_context: false,
+            loop_depth: 0,
+            cleanup: false,
+            conditional: false,
+            arg_tys: Vec::new(),
+            span: None,
         }
     }
 }
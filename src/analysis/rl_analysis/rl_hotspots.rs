@@ -0,0 +1,222 @@
+use petgraph::visit::EdgeRef;
+use rustc_data_structures::fx::FxIndexMap;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use super::rl_petgraph::{call_graph, RLCallGraph};
+
+/// The damping factor of PageRank, i.e., the probability of following an edge instead of
+/// jumping to a random node.
+const PAGE_RANK_DAMPING: f64 = 0.85;
+const PAGE_RANK_ITERATIONS: usize = 100;
+/// The relative tolerance under which two lengths of paths are the same for the betweenness.
+const BETWEENNESS_EPSILON: f64 = 1e-9;
+
+/// The centrality scores of a node, computed on the edges weighted by their ownership score
+/// (see `RLEdge::ownership_score`). The betweenness counts the shortest paths where the length
+/// of a call is the inverse of its ownership score.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct RLCentrality {
+    page_rank: f64,
+    betweenness: f64,
+    in_degree: usize,
+    out_degree: usize,
+}
+
+impl RLCentrality {
+    pub fn new(page_rank: f64, betweenness: f64, in_degree: usize, out_degree: usize) -> Self {
+        Self {
+            page_rank,
+            betweenness,
+            in_degree,
+            out_degree,
+        }
+    }
+
+    /// The centrality scores of each function of the graph, sorted by decreasing PageRank.
+    pub fn collect<G>(rl_graph: &G) -> Vec<(RLIndex, Self)>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let call_graph = call_graph(rl_graph);
+        let page_ranks = weighted_page_rank(&call_graph);
+        let betweenness = weighted_betweenness(&call_graph);
+
+        let mut centralities = call_graph
+            .node_indices()
+            .map(|node| {
+                let in_degree = call_graph
+                    .edges_directed(node, petgraph::Direction::Incoming)
+                    .map(|edge| edge.weight().ownership_score())
+                    .sum();
+                let out_degree = call_graph
+                    .edges(node)
+                    .map(|edge| edge.weight().ownership_score())
+                    .sum();
+                let centrality = Self::new(
+                    page_ranks[node.index()],
+                    betweenness[node.index()],
+                    in_degree,
+                    out_degree,
+                );
+                (call_graph[node], centrality)
+            })
+            .collect::<Vec<_>>();
+        centralities.sort_by(|(a_node, a), (b_node, b)| {
+            b.page_rank()
+                .total_cmp(&a.page_rank())
+                .then(a_node.cmp(b_node))
+        });
+        centralities
+    }
+
+    pub fn page_rank(&self) -> f64 {
+        self.page_rank
+    }
+
+    pub fn betweenness(&self) -> f64 {
+        self.betweenness
+    }
+
+    pub fn in_degree(&self) -> usize {
+        self.in_degree
+    }
+
+    pub fn out_degree(&self) -> usize {
+        self.out_degree
+    }
+}
+
+impl std::fmt::Display for RLCentrality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "PageRank {:.4}, betweenness {:.4}, in-degree {}, out-degree {}",
+            self.page_rank, self.betweenness, self.in_degree, self.out_degree
+        )
+    }
+}
+
+/// Compute the PageRank of each node, following each edge with a probability proportional
+/// to its ownership score. The rank of the nodes without outgoing edges is spread over all
+/// the nodes.
+/// `rustworkx_core` does not provide PageRank, and the one of `petgraph` is unweighted.
+fn weighted_page_rank(graph: &RLCallGraph<'_>) -> Vec<f64> {
+    let node_count = graph.node_count();
+    if node_count == 0 {
+        return Vec::new();
+    }
+    let out_scores = graph
+        .node_indices()
+        .map(|node| {
+            graph
+                .edges(node)
+                .map(|edge| edge.weight().ownership_score())
+                .sum::<usize>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut ranks = vec![1.0 / node_count as f64; node_count];
+    for _ in 0..PAGE_RANK_ITERATIONS {
+        let dangling_rank = graph
+            .node_indices()
+            .filter(|node| out_scores[node.index()] == 0)
+            .map(|node| ranks[node.index()])
+            .sum::<f64>();
+        let base_rank = (1.0 - PAGE_RANK_DAMPING) / node_count as f64
+            + PAGE_RANK_DAMPING * dangling_rank / node_count as f64;
+        let mut next_ranks = vec![base_rank; node_count];
+        for edge in graph.edge_references() {
+            let source = edge.source().index();
+            next_ranks[edge.target().index()] +=
+                PAGE_RANK_DAMPING * ranks[source] * edge.weight().ownership_score() as f64
+                    / out_scores[source] as f64;
+        }
+        ranks = next_ranks;
+    }
+    ranks
+}
+
+/// Compute the betweenness of each node on the shortest paths where the length of a call is
+/// the inverse of its ownership score, so the paths moving more ownership are the shortest.
+/// It is the algorithm of Brandes with Dijkstra, normalized as the one of `rustworkx_core`
+/// (which is unweighted) for the directed graphs without the endpoints.
+fn weighted_betweenness(graph: &RLCallGraph<'_>) -> Vec<f64> {
+    let node_count = graph.node_count();
+    // The parallel calls between two functions are a single step of the shortest length.
+    let successors = graph
+        .node_indices()
+        .map(|node| {
+            let mut successors: FxIndexMap<usize, f64> = FxIndexMap::default();
+            for edge in graph.edges(node) {
+                let length = 1.0 / edge.weight().ownership_score() as f64;
+                let successor = successors
+                    .entry(edge.target().index())
+                    .or_insert(f64::INFINITY);
+                *successor = successor.min(length);
+            }
+            successors
+        })
+        .collect::<Vec<_>>();
+    let is_same_length = |a: f64, b: f64| (a - b).abs() <= BETWEENNESS_EPSILON * a.max(b);
+
+    let mut betweenness = vec![0.0; node_count];
+    for source in 0..node_count {
+        // The nodes in order of distance, with the shortest paths reaching each node
+        // (`path_counts`) and its predecessors on them.
+        let mut visited = Vec::new();
+        let mut predecessors = vec![Vec::new(); node_count];
+        let mut path_counts = vec![0.0; node_count];
+        let mut distances: Vec<Option<f64>> = vec![None; node_count];
+        let mut tentative = vec![f64::INFINITY; node_count];
+        path_counts[source] = 1.0;
+        tentative[source] = 0.0;
+        // The bits of the non-negative lengths are ordered as the lengths.
+        let mut queue = BinaryHeap::from([Reverse((0.0f64.to_bits(), source))]);
+        while let Some(Reverse((distance, node))) = queue.pop() {
+            if distances[node].is_some() {
+                continue;
+            }
+            let distance = f64::from_bits(distance);
+            distances[node] = Some(distance);
+            visited.push(node);
+            for (&successor, &length) in &successors[node] {
+                if distances[successor].is_some() {
+                    continue;
+                }
+                let successor_distance = distance + length;
+                if tentative[successor].is_finite()
+                    && is_same_length(successor_distance, tentative[successor])
+                {
+                    path_counts[successor] += path_counts[node];
+                    predecessors[successor].push(node);
+                } else if successor_distance < tentative[successor] {
+                    tentative[successor] = successor_distance;
+                    path_counts[successor] = path_counts[node];
+                    predecessors[successor] = vec![node];
+                    queue.push(Reverse((successor_distance.to_bits(), successor)));
+                }
+            }
+        }
+
+        let mut dependencies = vec![0.0; node_count];
+        while let Some(node) = visited.pop() {
+            for &predecessor in &predecessors[node] {
+                dependencies[predecessor] +=
+                    path_counts[predecessor] / path_counts[node] * (1.0 + dependencies[node]);
+            }
+            if node != source {
+                betweenness[node] += dependencies[node];
+            }
+        }
+    }
+
+    if node_count > 2 {
+        let scale = 1.0 / ((node_count - 1) * (node_count - 2)) as f64;
+        for value in &mut betweenness {
+            *value *= scale;
+        }
+    }
+    betweenness
+}
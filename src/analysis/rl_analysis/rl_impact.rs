@@ -0,0 +1,205 @@
+use rustc_hash::FxHashMap;
+
+use super::rl_context::RLDefSpan;
+
+/// The lines changed in each file since a revision, read from `git diff`.
+#[derive(Debug, Clone, Default)]
+pub struct RLDiff {
+    /// The ranges `(first_line, last_line)` of the changed lines of each file,
+    /// in the current version of the file.
+    changed_lines: FxHashMap<String, Vec<(usize, usize)>>,
+}
+
+impl RLDiff {
+    /// Run `git diff` between the revision `since` and the working tree.
+    /// The untracked files (except the ignored ones), which `git diff` omits, are changed
+    /// on all their lines.
+    /// The paths are relative to the current directory, like the ones printed by the compiler
+    /// for the crates of the workspace.
+    /// It fails if `git` cannot be run, e.g., outside a git repository or with an unknown
+    /// revision.
+    pub fn since(since: &str) -> Result<Self, String> {
+        let mut diff = Self::parse(&git(&[
+            "diff",
+            "--relative",
+            "--unified=0",
+            "--no-color",
+            since,
+        ])?)?;
+        let untracked = git(&["ls-files", "--others", "--exclude-standard"])?;
+        diff.add_untracked(&untracked);
+        Ok(diff)
+    }
+
+    /// Add the untracked files listed by `git ls-files --others`, one per line.
+    fn add_untracked(&mut self, files: &str) {
+        for file in files.lines().filter(|file| !file.is_empty()) {
+            self.changed_lines
+                .entry(file.to_string())
+                .or_default()
+                .push((1, usize::MAX));
+        }
+    }
+
+    /// Parse the hunks of a diff without context (`--unified=0`).
+    /// A hunk which only deletes lines changes the line preceding the deleted ones.
+    fn parse(diff: &str) -> Result<Self, String> {
+        let mut changed_lines: FxHashMap<String, Vec<(usize, usize)>> = FxHashMap::default();
+        let mut file = None;
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                // A deleted file has no functions left.
+                file = path.strip_prefix("b/").map(str::to_string);
+            } else if let Some(hunk) = line.strip_prefix("@@ ") {
+                let Some(file) = &file else {
+                    continue;
+                };
+                // E.g., `@@ -3,2 +3,0 @@ fn parse()`, where the range after `+` is the one
+                // of the new version, with 1 line if its length is omitted.
+                let Some(new_range) = hunk
+                    .split_whitespace()
+                    .find_map(|range| range.strip_prefix('+'))
+                else {
+                    continue;
+                };
+                let (first_line, len) = match new_range.split_once(',') {
+                    Some((first_line, len)) => (first_line, len),
+                    None => (new_range, "1"),
+                };
+                let invalid_hunk = |_| format!("Invalid hunk header `{}`", line);
+                let first_line = first_line.parse::<usize>().map_err(invalid_hunk)?;
+                let len = len.parse::<usize>().map_err(invalid_hunk)?;
+                let range = match len {
+                    0 => (first_line, first_line),
+                    len => (first_line, first_line + len - 1),
+                };
+                changed_lines.entry(file.clone()).or_default().push(range);
+            }
+        }
+        Ok(Self { changed_lines })
+    }
+
+    /// Whether a changed line is in the definition.
+    pub fn touches(&self, def_span: &RLDefSpan) -> bool {
+        self.changed_lines
+            .get(def_span.file())
+            .is_some_and(|ranges| {
+                ranges.iter().any(|(first_line, last_line)| {
+                    *first_line <= def_span.last_line() && def_span.first_line() <= *last_line
+                })
+            })
+    }
+}
+
+/// Run `git` with the arguments in the current directory, returning its output.
+fn git(args: &[&str]) -> Result<String, String> {
+    let command = format!("git {}", args.join(" "));
+    let output = std::process::Command::new("git")
+        .args(args)
+        .output()
+        .map_err(|error| format!("Failed to run `{}`: {}", command, error))?;
+    if !output.status.success() {
+        // Outside a git repository, the first line is followed by the usage of the command.
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!(
+            "Failed to run `{}`: {}",
+            command,
+            stderr.lines().next().unwrap_or_default()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changed_lines(diff: &RLDiff, file: &str) -> Option<Vec<(usize, usize)>> {
+        diff.changed_lines.get(file).cloned()
+    }
+
+    #[test]
+    fn test_omitted_counts() {
+        let diff = RLDiff::parse(
+            "diff --git a/src/lib.rs b/src/lib.rs\n\
+             --- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -3 +3 @@ fn main() {\n\
+             -    a();\n\
+             +    b();\n\
+             @@ -10,2 +10 @@\n",
+        )
+        .unwrap();
+        assert_eq!(
+            changed_lines(&diff, "src/lib.rs"),
+            Some(vec![(3, 3), (10, 10)])
+        );
+    }
+
+    #[test]
+    fn test_zero_length_hunk() {
+        let diff = RLDiff::parse(
+            "--- a/src/lib.rs\n\
+             +++ b/src/lib.rs\n\
+             @@ -5,2 +4,0 @@\n\
+             -    a();\n\
+             -    b();\n\
+             @@ -8,0 +7,3 @@\n",
+        )
+        .unwrap();
+        assert_eq!(
+            changed_lines(&diff, "src/lib.rs"),
+            Some(vec![(4, 4), (7, 9)])
+        );
+    }
+
+    #[test]
+    fn test_renamed_file() {
+        let diff = RLDiff::parse(
+            "diff --git a/src/old.rs b/src/new.rs\n\
+             similarity index 90%\n\
+             rename from src/old.rs\n\
+             rename to src/new.rs\n\
+             --- a/src/old.rs\n\
+             +++ b/src/new.rs\n\
+             @@ -2,2 +2,2 @@\n",
+        )
+        .unwrap();
+        assert_eq!(changed_lines(&diff, "src/old.rs"), None);
+        assert_eq!(changed_lines(&diff, "src/new.rs"), Some(vec![(2, 3)]));
+    }
+
+    #[test]
+    fn test_dev_null() {
+        let diff = RLDiff::parse(
+            "diff --git a/src/new.rs b/src/new.rs\n\
+             new file mode 100644\n\
+             --- /dev/null\n\
+             +++ b/src/new.rs\n\
+             @@ -0,0 +1,4 @@\n\
+             diff --git a/src/deleted.rs b/src/deleted.rs\n\
+             deleted file mode 100644\n\
+             --- a/src/deleted.rs\n\
+             +++ /dev/null\n\
+             @@ -1,3 +0,0 @@\n",
+        )
+        .unwrap();
+        assert_eq!(changed_lines(&diff, "src/new.rs"), Some(vec![(1, 4)]));
+        assert_eq!(changed_lines(&diff, "src/deleted.rs"), None);
+        assert_eq!(diff.changed_lines.len(), 1);
+    }
+
+    #[test]
+    fn test_untracked_files() {
+        let mut diff = RLDiff::parse("").unwrap();
+        diff.add_untracked("src/untracked.rs\ntests/untracked.rs\n");
+        assert_eq!(
+            changed_lines(&diff, "src/untracked.rs"),
+            Some(vec![(1, usize::MAX)])
+        );
+        assert_eq!(
+            changed_lines(&diff, "tests/untracked.rs"),
+            Some(vec![(1, usize::MAX)])
+        );
+    }
+}
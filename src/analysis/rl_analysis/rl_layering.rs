@@ -0,0 +1,129 @@
+use serde::Deserialize;
+
+use super::rl_calls::{matches_path, rules_from_file, RLCall};
+use super::rl_condensed::{Granularity, RLCondenser};
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// The rules of the allowed dependencies between the modules and the crates,
+/// read from a TOML file with a `[[rule]]` table for each rule:
+/// ```toml
+/// [[rule]]
+/// name = "the domain must not call the infrastructure"
+/// from = ["app::domain"]
+/// to = ["app::infra"]
+///
+/// [[rule]]
+/// name = "only the adapters can call the legacy code"
+/// to = ["app::legacy::*"]
+/// except = ["app::adapters"]
+/// ```
+/// A call violates a rule if the caller matches `from` (any caller if it is empty),
+/// the callee matches `to` and the caller matches neither `to` nor `except`.
+/// A pattern is the path of an item with its crate, matching the item and everything
+/// defined inside it (e.g., `app::legacy` and `app::legacy::*` match `app::legacy::parse`).
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RLLayeringRules {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RLLayeringRule>,
+}
+
+// A misspelled key (e.g., `exept`) is an error, since it would silently change the rule.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RLLayeringRule {
+    name: String,
+    #[serde(default)]
+    from: Vec<String>,
+    to: Vec<String>,
+    #[serde(default)]
+    except: Vec<String>,
+}
+
+impl RLLayeringRules {
+    pub fn from_file(file_path: &str) -> Result<Self, String> {
+        rules_from_file(file_path)
+    }
+}
+
+impl RLLayeringRule {
+    fn is_violated_by(&self, caller: &str, callee: &str) -> bool {
+        let matches_any =
+            |patterns: &[String], path: &str| patterns.iter().any(|p| matches_path(p, path));
+        (self.from.is_empty() || matches_any(&self.from, caller))
+            && matches_any(&self.to, callee)
+            && !matches_any(&self.to, caller)
+            && !matches_any(&self.except, caller)
+    }
+}
+
+/// A call violating a layering rule.
+#[derive(Debug, Clone)]
+pub struct RLLayeringViolation {
+    rule: String,
+    caller: String,
+    callee: String,
+    span: Option<String>,
+}
+
+impl std::fmt::Display for RLLayeringViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "`{}`: {} -> {} ({})",
+            self.rule,
+            self.caller,
+            self.callee,
+            self.span.as_deref().unwrap_or("unknown span")
+        )
+    }
+}
+
+/// `RLLayeringChecker` checks the calls of the graph against the layering rules and finds
+/// the cycles between the modules.
+///
+/// Each call is in the graph of the crate of its caller, so checking the graph of each
+/// crate finds all the violations of the merged graph of a workspace, and the cycles too,
+/// since the dependencies between the crates cannot be cyclic.
+pub struct RLLayeringChecker {
+    rules: RLLayeringRules,
+}
+
+impl RLLayeringChecker {
+    pub fn new(rules: RLLayeringRules) -> Self {
+        Self { rules }
+    }
+
+    /// The calls violating a rule, in the order of the rules.
+    /// The statically unknown call sites are skipped, since their callee is unknown.
+    pub fn violations<G>(&self, rl_graph: &G) -> Vec<RLLayeringViolation>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let calls = RLCall::collect(rl_graph);
+
+        let mut violations = Vec::new();
+        for rule in &self.rules.rules {
+            for call in &calls {
+                if rule.is_violated_by(call.caller(), call.callee()) {
+                    violations.push(RLLayeringViolation {
+                        rule: rule.name.clone(),
+                        caller: call.caller().to_string(),
+                        callee: call.callee().to_string(),
+                        span: call.edge().span().map(str::to_string),
+                    });
+                }
+            }
+        }
+        violations
+    }
+
+    /// The cycles between the modules of the crate.
+    pub fn module_cycles<G>(&self, rl_graph: &G) -> Vec<Vec<String>>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let condenser = RLCondenser::new(Granularity::Module);
+        rl_graph.condense(&|node| condenser.group_of(node)).cycles()
+    }
+}
@@ -0,0 +1,466 @@
+use rustc_abi::ExternAbi;
+use rustc_data_structures::fx::FxHashMap;
+use rustc_hir::def::DefKind;
+use rustc_middle::{
+    mir::{self, traversal},
+    ty::{self, TyCtxt},
+};
+use rustc_span::def_id::{DefId, LocalDefId};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use super::rl_context::{CallKind, OperandKind};
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// The maximum number of moves followed for a single value, to stop on recursive functions.
+const MAX_CHAIN_STEPS: usize = 64;
+
+/// An owner of a value: the local (variable or argument) of a function that holds it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RLOwner {
+    function: String,
+    local: String,
+}
+
+impl std::fmt::Display for RLOwner {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}", self.function, self.local)
+    }
+}
+
+/// How an ownership chain ends.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum RLOwnershipEnd {
+    /// The value is dropped by its last owner.
+    Dropped,
+    /// The value is stored in a field, in an aggregate or behind a pointer.
+    Stored,
+    /// The value is returned by the function which created it.
+    Returned,
+    /// The value is moved into a function whose body was not analyzed (e.g., `Vec::push`).
+    MovedInto(String),
+    /// The value is moved more than `MAX_CHAIN_STEPS` times (e.g., in a recursive function).
+    Truncated,
+}
+
+impl std::fmt::Display for RLOwnershipEnd {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RLOwnershipEnd::Dropped => write!(f, "dropped"),
+            RLOwnershipEnd::Stored => write!(f, "stored"),
+            RLOwnershipEnd::Returned => write!(f, "returned"),
+            RLOwnershipEnd::MovedInto(function) => write!(f, "moved into `{}`", function),
+            RLOwnershipEnd::Truncated => write!(f, "truncated"),
+        }
+    }
+}
+
+/// The ownership chain of a value: the owners it is moved through, from the function
+/// which creates it to the one which drops or stores it.
+/// The temporaries of the MIR are not owners, only the variables and the arguments are.
+#[derive(Debug, Clone)]
+pub struct RLOwnershipChain {
+    ty: String,
+    owners: Vec<RLOwner>,
+    end: RLOwnershipEnd,
+}
+
+impl RLOwnershipChain {
+    pub fn len(&self) -> usize {
+        self.owners.len()
+    }
+}
+
+impl std::fmt::Display for RLOwnershipChain {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        // A value created in a temporary can reach its end without a named owner.
+        let steps = self
+            .owners
+            .iter()
+            .map(|owner| owner.to_string())
+            .chain(std::iter::once(self.end.to_string()))
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "`{}` (length {}): {}",
+            self.ty,
+            self.len(),
+            steps.join(" -> ")
+        )
+    }
+}
+
+/// How a local is consumed in the body of a function.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum RLConsumption {
+    /// The local is moved into another local of the same function.
+    Moved(u32),
+    /// The local is passed by value as the argument `arg` of the call at `span`,
+    /// whose result is assigned to the local `destination` (`None` if it is not a local).
+    /// The `callee` is the function called, used when its body was not analyzed.
+    Passed {
+        arg: usize,
+        span: String,
+        callee: String,
+        destination: Option<u32>,
+    },
+    End(RLOwnershipEnd),
+}
+
+/// `RLOwnershipFlow` contains the ownership facts of the body of a function needed to
+/// follow its values (see `RLOwnershipTracer`):
+/// - the locals which receive a value created by an aggregate (e.g., `Buffer { data }`)
+///   or by a constructor call (e.g., `Buffer::new()`), with the type of the value;
+/// - the first consuming use of each local in reverse postorder, so a value moved on
+///   different branches is followed only on one of them;
+/// - the names of the variables and arguments.
+///
+/// Only the locals of non-`Copy` ADTs are recorded.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RLOwnershipFlow {
+    function: String,
+    origins: Vec<(u32, String)>,
+    consumptions: BTreeMap<u32, RLConsumption>,
+    names: BTreeMap<u32, String>,
+}
+
+impl RLOwnershipFlow {
+    /// Collect the ownership facts of the body of `local_def_id`.
+    /// It returns `None` if the body is not a function or a closure.
+    pub fn collect<'tcx>(
+        tcx: TyCtxt<'tcx>,
+        local_def_id: LocalDefId,
+        body: &mir::Body<'tcx>,
+    ) -> Option<Self> {
+        let def_id = local_def_id.to_def_id();
+        if !tcx.def_kind(def_id).is_fn_like() {
+            return None;
+        }
+        let typing_env = ty::TypingEnv::post_analysis(tcx, def_id);
+        let is_owned = |local: mir::Local| {
+            let ty = body.local_decls[local].ty;
+            ty.is_adt() && !tcx.type_is_copy_modulo_regions(typing_env, ty)
+        };
+
+        let mut flow = Self {
+            function: tcx.def_path_str(def_id),
+            ..Self::default()
+        };
+        let mut consumptions = FxHashMap::default();
+        // The locals moved into each tuple, e.g., the arguments of a closure call.
+        let mut tuples: FxHashMap<mir::Local, Vec<mir::Local>> = FxHashMap::default();
+        for (_, data) in traversal::reverse_postorder(body) {
+            for statement in &data.statements {
+                let mir::StatementKind::Assign(assign) = &statement.kind else {
+                    continue;
+                };
+                let (place, rvalue) = &**assign;
+                match rvalue {
+                    mir::Rvalue::Use(operand) | mir::Rvalue::Cast(_, operand, _) => {
+                        if let Some(local) = moved_local(operand) {
+                            let consumption = match place.as_local() {
+                                Some(destination) => RLConsumption::Moved(destination.as_u32()),
+                                None => RLConsumption::End(RLOwnershipEnd::Stored),
+                            };
+                            consumptions.entry(local).or_insert(consumption);
+                        }
+                    }
+                    mir::Rvalue::Aggregate(aggregate_kind, operands) => {
+                        for local in operands.iter().filter_map(moved_local) {
+                            consumptions
+                                .entry(local)
+                                .or_insert(RLConsumption::End(RLOwnershipEnd::Stored));
+                        }
+                        match **aggregate_kind {
+                            mir::AggregateKind::Adt(..) => flow.add_origin(place, body),
+                            mir::AggregateKind::Tuple => {
+                                if let Some(tuple) = place.as_local() {
+                                    tuples.insert(
+                                        tuple,
+                                        operands.iter().filter_map(moved_local).collect(),
+                                    );
+                                }
+                            }
+                            _ => {}
+                        }
+                    }
+                    _ => {}
+                }
+            }
+
+            match &data.terminator().kind {
+                mir::TerminatorKind::Call {
+                    func,
+                    args,
+                    destination,
+                    ..
+                } => {
+                    let callee = func.const_fn_def();
+                    // The arguments of the closures and of the `Fn*` methods are tupled, so
+                    // they do not match the parameters of the callee: the values passed to
+                    // them, also inside the tuple, are not followed into the callee.
+                    if let Some((callee, _)) =
+                        callee.filter(|(callee, _)| is_rust_call(tcx, *callee))
+                    {
+                        let end = RLOwnershipEnd::MovedInto(tcx.def_path_str(callee));
+                        for operand in args.iter() {
+                            let Some(local) = moved_local(&operand.node) else {
+                                continue;
+                            };
+                            for tupled in tuples.get(&local).into_iter().flatten() {
+                                consumptions.insert(*tupled, RLConsumption::End(end.clone()));
+                            }
+                            consumptions
+                                .entry(local)
+                                .or_insert(RLConsumption::End(end.clone()));
+                        }
+                        continue;
+                    }
+                    for (arg, local) in args
+                        .iter()
+                        .enumerate()
+                        .filter_map(|(arg, operand)| Some((arg, moved_local(&operand.node)?)))
+                    {
+                        let consumption = match callee {
+                            Some((callee, _)) => RLConsumption::Passed {
+                                arg,
+                                span: tcx
+                                    .sess
+                                    .source_map()
+                                    .span_to_embeddable_string(data.terminator().source_info.span),
+                                callee: tcx.def_path_str(callee),
+                                destination: destination.as_local().map(mir::Local::as_u32),
+                            },
+                            // The callee is a function pointer or a `dyn` method.
+                            None => RLConsumption::End(RLOwnershipEnd::Stored),
+                        };
+                        consumptions.entry(local).or_insert(consumption);
+                    }
+                    if callee.is_some_and(|(callee, _)| is_constructor(tcx, callee)) {
+                        flow.add_origin(destination, body);
+                    }
+                }
+                mir::TerminatorKind::Drop { place, .. } => {
+                    consumptions
+                        .entry(place.local)
+                        .or_insert(RLConsumption::End(RLOwnershipEnd::Dropped));
+                }
+                _ => {}
+            }
+        }
+
+        // A value assigned to the return place is created for the caller, whose call to
+        // the constructor is the origin.
+        flow.origins
+            .retain(|(local, _)| *local != mir::RETURN_PLACE.as_u32());
+        flow.origins
+            .retain(|(local, _)| is_owned(mir::Local::from_u32(*local)));
+        flow.consumptions = consumptions
+            .into_iter()
+            .filter(|(local, _)| is_owned(*local))
+            .map(|(local, consumption)| (local.as_u32(), consumption))
+            .collect();
+        flow.names = body
+            .var_debug_info
+            .iter()
+            .filter_map(|var_debug_info| match var_debug_info.value {
+                mir::VarDebugInfoContents::Place(place) => place
+                    .as_local()
+                    .filter(|local| is_owned(*local))
+                    .map(|local| (local.as_u32(), var_debug_info.name.to_string())),
+                _ => None,
+            })
+            .collect();
+        Some(flow)
+    }
+
+    fn add_origin(&mut self, place: &mir::Place<'_>, body: &mir::Body<'_>) {
+        if let Some(local) = place.as_local() {
+            self.origins
+                .push((local.as_u32(), body.local_decls[local].ty.to_string()));
+        }
+    }
+
+    /// The first consuming use of the `local`. A value which is never moved is dropped
+    /// at the end of its scope (without a `Drop` terminator if it has no drop glue).
+    fn consumption(&self, local: u32) -> RLConsumption {
+        self.consumptions
+            .get(&local)
+            .cloned()
+            .unwrap_or(RLConsumption::End(RLOwnershipEnd::Dropped))
+    }
+
+    /// The name of the variable or argument, `None` for the temporaries.
+    fn name(&self, local: u32) -> Option<&str> {
+        self.names.get(&local).map(String::as_str)
+    }
+}
+
+/// The local moved by the operand, if it is moved as a whole.
+fn moved_local(operand: &mir::Operand<'_>) -> Option<mir::Local> {
+    match operand {
+        mir::Operand::Move(place) => place.as_local(),
+        _ => None,
+    }
+}
+
+/// Whether the function takes its arguments tupled (the `rust-call` ABI), e.g., `Fn::call`.
+fn is_rust_call(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    tcx.fn_sig(def_id).skip_binder().abi() == ExternAbi::RustCall
+}
+
+/// A constructor is a tuple struct or variant constructor, or an associated function
+/// of an inherent `impl` which returns the type of the `impl` (e.g., `Vec::new`).
+fn is_constructor(tcx: TyCtxt<'_>, def_id: DefId) -> bool {
+    match tcx.def_kind(def_id) {
+        DefKind::Ctor(..) => true,
+        DefKind::AssocFn => match tcx.impl_of_method(def_id) {
+            Some(impl_def_id) if tcx.trait_id_of_impl(impl_def_id).is_none() => {
+                let output = tcx
+                    .fn_sig(def_id)
+                    .instantiate_identity()
+                    .skip_binder()
+                    .output();
+                output == tcx.type_of(impl_def_id).instantiate_identity()
+            }
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// `RLOwnershipTracer` follows each value created by an aggregate or by a constructor
+/// call (see `RLOwnershipFlow`) through its moves, across the calls of the graph, until
+/// it is dropped or stored.
+///
+/// A value passed by value to a function follows the edge of the call to the callee,
+/// where it is owned by the corresponding parameter, so on the merged graph of the
+/// workspace the chains cross the crates.
+pub struct RLOwnershipTracer<'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    rl_graph: &'a G,
+    /// The callees of the calls passing an argument by value, by caller and call site.
+    callees: FxHashMap<(RLIndex, &'a str), Vec<(RLIndex, &'a RLEdge)>>,
+}
+
+impl<'a, G> RLOwnershipTracer<'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    pub fn new(rl_graph: &'a G) -> Self {
+        let mut callees: FxHashMap<_, Vec<_>> = FxHashMap::default();
+        for (caller, callee, edge) in rl_graph.rl_edges() {
+            if !matches!(edge.call_kind(), CallKind::Function | CallKind::Method) {
+                continue;
+            }
+            if let Some(span) = edge.span() {
+                callees
+                    .entry((caller, span))
+                    .or_default()
+                    .push((callee, edge));
+            }
+        }
+        Self { rl_graph, callees }
+    }
+
+    pub fn chains(&self) -> Vec<RLOwnershipChain> {
+        let mut chains = Vec::new();
+        for (index, node) in self.rl_graph.rl_nodes() {
+            let Some(flow) = node.ownership_flow() else {
+                continue;
+            };
+            for (local, ty) in &flow.origins {
+                chains.push(self.chain(index, *local, ty));
+            }
+        }
+        chains
+    }
+
+    fn chain(&self, node: RLIndex, local: u32, ty: &str) -> RLOwnershipChain {
+        let mut owners = Vec::new();
+        // The callers to return to, with the local receiving the returned value.
+        let mut return_stack: Vec<(RLIndex, Option<u32>)> = Vec::new();
+        let (mut node, mut local) = (node, local);
+        let mut steps = 0;
+
+        let end = loop {
+            if steps == MAX_CHAIN_STEPS {
+                break RLOwnershipEnd::Truncated;
+            }
+            steps += 1;
+            let flow = self
+                .rl_graph
+                .rl_node(node)
+                .ownership_flow()
+                .expect("The chains visit only the functions with an ownership flow");
+            if let Some(name) = flow.name(local) {
+                owners.push(RLOwner {
+                    function: flow.function.clone(),
+                    local: name.to_string(),
+                });
+            }
+
+            match flow.consumption(local) {
+                RLConsumption::Moved(destination) if destination == mir::RETURN_PLACE.as_u32() => {
+                    match Self::return_to(&mut return_stack) {
+                        Ok((caller, destination)) => (node, local) = (caller, destination),
+                        Err(end) => break end,
+                    }
+                }
+                RLConsumption::Moved(destination) => local = destination,
+                RLConsumption::Passed {
+                    arg,
+                    span,
+                    callee,
+                    destination,
+                } => match self.callee_with_flow(node, arg, &span) {
+                    Some(callee) => {
+                        return_stack.push((node, destination));
+                        // The local `_0` is the return place, followed by the parameters.
+                        (node, local) = (callee, arg as u32 + 1);
+                    }
+                    None => break RLOwnershipEnd::MovedInto(callee),
+                },
+                RLConsumption::End(end) => break end,
+            }
+        };
+
+        RLOwnershipChain {
+            ty: ty.to_string(),
+            owners,
+            end,
+        }
+    }
+
+    /// The callee of the call at `span` of the `caller` which moves its argument `arg`,
+    /// if its body was analyzed (i.e., it has an ownership flow).
+    fn callee_with_flow(&self, caller: RLIndex, arg: usize, span: &str) -> Option<RLIndex> {
+        self.callees
+            .get(&(caller, span))?
+            .iter()
+            .find(|(callee, edge)| {
+                edge.arg_weights()
+                    .get(arg)
+                    .is_some_and(|(operand, _, _)| *operand == OperandKind::Move)
+                    && self.rl_graph.rl_node(*callee).ownership_flow().is_some()
+            })
+            .map(|(callee, _)| *callee)
+    }
+
+    /// The caller and the local receiving a returned value.
+    /// A value returned by the caller too is returned to its own caller.
+    fn return_to(
+        return_stack: &mut Vec<(RLIndex, Option<u32>)>,
+    ) -> Result<(RLIndex, u32), RLOwnershipEnd> {
+        while let Some((caller, destination)) = return_stack.pop() {
+            match destination {
+                Some(destination) if destination == mir::RETURN_PLACE.as_u32() => continue,
+                Some(destination) => return Ok((caller, destination)),
+                None => return Err(RLOwnershipEnd::Stored),
+            }
+        }
+        Err(RLOwnershipEnd::Returned)
+    }
+}
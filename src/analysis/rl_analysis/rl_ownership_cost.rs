@@ -0,0 +1,100 @@
+use petgraph::visit::EdgeRef;
+use serde::{Deserialize, Serialize};
+
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use super::rl_petgraph::call_graph;
+
+/// The ownership score of a clone, i.e., of a call moving a composite value out of a borrow.
+pub const CLONE_OWNERSHIP_SCORE: usize = 5;
+
+/// The ownership cost of a function.
+///
+/// The own cost is the sum of the ownership scores of its calls (see `RLEdge::ownership_score`),
+/// plus `CLONE_OWNERSHIP_SCORE` for each clone.
+/// The transitive cost adds the transitive cost of the callees, multiplied by a damping factor.
+/// The functions of a strongly connected component (i.e., the recursive functions) reach each
+/// other, so they share the same transitive cost: the sum of their own costs plus the damped
+/// transitive cost of the calls leaving the component.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RLOwnershipCost {
+    own: usize,
+    transitive: f64,
+}
+
+impl RLOwnershipCost {
+    pub fn new(own: usize, transitive: f64) -> Self {
+        Self { own, transitive }
+    }
+
+    /// The ownership cost of each function of the graph, sorted by decreasing transitive cost.
+    /// The cost of the callees is multiplied by `damping` at each call.
+    pub fn collect<G>(rl_graph: &G, damping: f64) -> Vec<(RLIndex, Self)>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let call_graph = call_graph(rl_graph);
+        let own_costs = call_graph
+            .node_indices()
+            .map(|node| {
+                call_graph
+                    .edges(node)
+                    .map(|edge| edge.weight().ownership_score())
+                    .sum::<usize>()
+                    + rl_graph.rl_node(call_graph[node]).clones().len() * CLONE_OWNERSHIP_SCORE
+            })
+            .collect::<Vec<_>>();
+
+        // The components are in reverse topological order, so the callees are summarized
+        // before their callers.
+        let mut transitive_costs = vec![0.0; call_graph.node_count()];
+        for scc in petgraph::algo::tarjan_scc(&call_graph) {
+            let mut transitive_cost = scc
+                .iter()
+                .map(|node| own_costs[node.index()] as f64)
+                .sum::<f64>();
+            for node in &scc {
+                for edge in call_graph.edges(*node) {
+                    if !scc.contains(&edge.target()) {
+                        transitive_cost += damping * transitive_costs[edge.target().index()];
+                    }
+                }
+            }
+            for node in &scc {
+                transitive_costs[node.index()] = transitive_cost;
+            }
+        }
+
+        let mut ownership_costs = call_graph
+            .node_indices()
+            .map(|node| {
+                let ownership_cost =
+                    Self::new(own_costs[node.index()], transitive_costs[node.index()]);
+                (call_graph[node], ownership_cost)
+            })
+            .collect::<Vec<_>>();
+        ownership_costs.sort_by(|(a_node, a), (b_node, b)| {
+            b.transitive()
+                .total_cmp(&a.transitive())
+                .then(a_node.cmp(b_node))
+        });
+        ownership_costs
+    }
+
+    pub fn own(&self) -> usize {
+        self.own
+    }
+
+    pub fn transitive(&self) -> f64 {
+        self.transitive
+    }
+}
+
+impl std::fmt::Display for RLOwnershipCost {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "transitive cost {:.2}, own cost {}",
+            self.transitive, self.own
+        )
+    }
+}
@@ -0,0 +1,223 @@
+use petgraph::visit::EdgeRef;
+use rustc_data_structures::fx::FxHashSet;
+use rustworkx_core::dictmap::DictMap;
+use rustworkx_core::petgraph::graph::NodeIndex;
+
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use super::rl_petgraph::{call_graph, RLCallGraph};
+
+/// The maximum number of functions between the two ends of a most expensive path.
+const MOST_EXPENSIVE_PATH_MAX_DEPTH: usize = 16;
+/// The maximum number of simple paths enumerated to find the most expensive ones.
+const MOST_EXPENSIVE_PATH_MAX_PATHS: usize = 10_000;
+
+/// A call of a path.
+#[derive(Debug, Clone)]
+pub struct RLPathCall<'a> {
+    caller: RLIndex,
+    callee: RLIndex,
+    edge: &'a RLEdge,
+}
+
+impl<'a> RLPathCall<'a> {
+    pub fn caller(&self) -> RLIndex {
+        self.caller
+    }
+
+    pub fn callee(&self) -> RLIndex {
+        self.callee
+    }
+
+    pub fn edge(&self) -> &'a RLEdge {
+        self.edge
+    }
+}
+
+/// A call path between two functions, with its cost, i.e., the sum of the ownership scores
+/// of its calls (see `RLEdge::ownership_score`).
+#[derive(Debug, Clone)]
+pub struct RLPath<'a> {
+    cost: usize,
+    calls: Vec<RLPathCall<'a>>,
+}
+
+impl<'a> RLPath<'a> {
+    pub fn cost(&self) -> usize {
+        self.cost
+    }
+
+    pub fn calls(&self) -> &[RLPathCall<'a>] {
+        &self.calls
+    }
+}
+
+/// `RLPathFinder` searches the `k` cheapest simple paths between two functions of the graph
+/// (with Yen's algorithm), or the `k` most expensive ones.
+///
+/// The most expensive paths are searched among a bounded number of simple paths with a
+/// bounded length, since enumerating all of them is exponential.
+/// Of the parallel calls between two functions, the cheapest (or the most expensive) one
+/// is taken.
+pub struct RLPathFinder<'a> {
+    call_graph: RLCallGraph<'a>,
+}
+
+impl<'a> RLPathFinder<'a> {
+    pub fn new<G>(rl_graph: &'a G) -> Self
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        Self {
+            call_graph: call_graph(rl_graph),
+        }
+    }
+
+    /// The `k` cheapest paths from `source` to `target`, or the `k` most expensive ones
+    /// with `most_expensive`.
+    pub fn paths(
+        &self,
+        source: RLIndex,
+        target: RLIndex,
+        k: usize,
+        most_expensive: bool,
+    ) -> Vec<RLPath<'a>> {
+        let graph = &self.call_graph;
+        let node_of = |index: RLIndex| graph.node_indices().find(|node| graph[*node] == index);
+        let (Some(source), Some(target)) = (node_of(source), node_of(target)) else {
+            return Vec::new();
+        };
+        if k == 0 {
+            return Vec::new();
+        }
+        let calls = |path: &[NodeIndex<RLIndex>]| {
+            path.windows(2)
+                .map(|hop| {
+                    let edges = graph.edges_connecting(hop[0], hop[1]);
+                    let edge = match most_expensive {
+                        true => edges.max_by_key(|edge| edge.weight().ownership_score()),
+                        false => edges.min_by_key(|edge| edge.weight().ownership_score()),
+                    }
+                    .expect("The nodes of a path are connected");
+                    RLPathCall {
+                        caller: graph[hop[0]],
+                        callee: graph[hop[1]],
+                        edge: edge.weight(),
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+        let cost_of = |path: &[NodeIndex<RLIndex>]| {
+            calls(path)
+                .iter()
+                .map(|call| call.edge.ownership_score())
+                .sum::<usize>()
+        };
+
+        let mut paths = Vec::new();
+        if most_expensive {
+            // The most expensive path is not a shortest path with negated costs, since the
+            // graph can have cycles, so the simple paths are enumerated. Their number grows
+            // exponentially with the size of the graph, so both their length and their number
+            // are bounded.
+            paths = petgraph::algo::all_simple_paths::<Vec<_>, _>(
+                graph,
+                source,
+                target,
+                0,
+                Some(MOST_EXPENSIVE_PATH_MAX_DEPTH),
+            )
+            .take(MOST_EXPENSIVE_PATH_MAX_PATHS)
+            .map(|path| (cost_of(&path), path))
+            .collect::<Vec<_>>();
+            paths.sort_by(|(a_cost, a), (b_cost, b)| b_cost.cmp(a_cost).then(a.cmp(b)));
+            paths.truncate(k);
+        } else if let Some(path) = cheapest_path(
+            graph,
+            source,
+            target,
+            &FxHashSet::default(),
+            &FxHashSet::default(),
+        ) {
+            // Yen's algorithm: each next path deviates from the previous one at one of its
+            // nodes (the spur node), avoiding the calls already taken from the same prefix.
+            paths.push((cost_of(&path), path));
+            let mut candidates: Vec<(usize, Vec<_>)> = Vec::new();
+            while paths.len() < k {
+                let (_, previous) = paths.last().unwrap().clone();
+                for i in 0..previous.len() - 1 {
+                    let root = &previous[..=i];
+                    let removed_edges = paths
+                        .iter()
+                        .filter(|(_, path)| path.len() > i + 1 && &path[..=i] == root)
+                        .map(|(_, path)| (path[i], path[i + 1]))
+                        .collect::<FxHashSet<_>>();
+                    let removed_nodes = previous[..i].iter().copied().collect::<FxHashSet<_>>();
+                    let Some(spur_path) =
+                        cheapest_path(graph, previous[i], target, &removed_nodes, &removed_edges)
+                    else {
+                        continue;
+                    };
+                    let path = previous[..i]
+                        .iter()
+                        .copied()
+                        .chain(spur_path)
+                        .collect::<Vec<_>>();
+                    if !candidates.iter().any(|(_, candidate)| *candidate == path) {
+                        candidates.push((cost_of(&path), path));
+                    }
+                }
+                let Some(cheapest) = candidates
+                    .iter()
+                    .enumerate()
+                    .min_by(|(_, a), (_, b)| a.cmp(b))
+                    .map(|(index, _)| index)
+                else {
+                    break;
+                };
+                paths.push(candidates.swap_remove(cheapest));
+            }
+        }
+
+        paths
+            .into_iter()
+            .map(|(cost, path)| RLPath {
+                cost,
+                calls: calls(&path),
+            })
+            .collect()
+    }
+}
+
+/// The cheapest path from `source` to `target` which avoids the `removed_nodes` and the
+/// `removed_edges`, i.e., the spur path of Yen's algorithm.
+fn cheapest_path(
+    graph: &RLCallGraph<'_>,
+    source: NodeIndex<RLIndex>,
+    target: NodeIndex<RLIndex>,
+    removed_nodes: &FxHashSet<NodeIndex<RLIndex>>,
+    removed_edges: &FxHashSet<(NodeIndex<RLIndex>, NodeIndex<RLIndex>)>,
+) -> Option<Vec<NodeIndex<RLIndex>>> {
+    // The removed edges cost infinity, so they are never part of a path to the `target`
+    // whose cost is finite.
+    let mut paths = DictMap::default();
+    let costs: DictMap<_, f64> = rustworkx_core::shortest_path::dijkstra(
+        graph,
+        source,
+        Some(target),
+        |edge| {
+            let is_removed = removed_nodes.contains(&edge.source())
+                || removed_nodes.contains(&edge.target())
+                || removed_edges.contains(&(edge.source(), edge.target()));
+            match is_removed {
+                true => Ok::<_, std::convert::Infallible>(f64::INFINITY),
+                false => Ok(edge.weight().ownership_score() as f64),
+            }
+        },
+        Some(&mut paths),
+    )
+    .expect("The cost of an edge is infallible");
+    match costs.get(&target) {
+        Some(cost) if cost.is_finite() => paths.swap_remove(&target),
+        _ => None,
+    }
+}
@@ -1,3 +1,4 @@
+use super::rl_condensed::RLCondensedGraph;
 use super::rl_graph::RLEdge;
 use super::rl_graph::RLGraph;
 use super::rl_graph::RLGraphIndex;
@@ -5,10 +6,36 @@ use super::rl_graph::RLGraphNode;
 use super::rl_graph::RLIndex;
 use super::rl_graph::RLNode;
 
+use super::rl_context::EntryPointKind;
+
 use petgraph::visit::EdgeRef;
+use rustc_data_structures::fx::FxHashSet;
+use rustc_hash::FxHashMap;
 use rustworkx_core::petgraph::csr::IndexType;
 use rustworkx_core::petgraph::graph;
 
+/// The calls of a graph as a graph of `petgraph`, to run its algorithms on any `RLGraph`.
+/// The weight of a node is the index of the function in the graph, and the weight of an edge
+/// is the call.
+pub type RLCallGraph<'a> = graph::DiGraph<RLIndex, &'a RLEdge, RLIndex>;
+
+/// Build the `RLCallGraph` of the graph, whose nodes are in the order of `RLGraph::rl_nodes`.
+pub fn call_graph<G>(rl_graph: &G) -> RLCallGraph<'_>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    let mut call_graph = RLCallGraph::default();
+    let nodes = rl_graph
+        .rl_nodes()
+        .into_iter()
+        .map(|(index, _)| (index, call_graph.add_node(index)))
+        .collect::<FxHashMap<_, _>>();
+    for (caller, callee, edge) in rl_graph.rl_edges() {
+        call_graph.add_edge(nodes[&caller], nodes[&callee], edge);
+    }
+    call_graph
+}
+
 unsafe impl IndexType for RLIndex {
     fn new(value: usize) -> Self {
         RLIndex::create(value)
@@ -48,11 +75,129 @@ impl RLGraph for graph::DiGraph<RLNode, RLEdge, RLIndex> {
         self.add_edge(source.into(), target.into(), edge);
     }
 
+    fn rl_node(&self, index: Self::Index) -> &Self::Node {
+        &self[graph::NodeIndex::from(index)]
+    }
+
+    fn rl_node_mut(&mut self, index: Self::Index) -> &mut Self::Node {
+        &mut self[graph::NodeIndex::from(index)]
+    }
+
+    fn unsafe_surface(&self) -> Vec<(Self::Index, Vec<Self::Index>)> {
+        // A node contains unsafe code if it is an `unsafe fn`, if it has an `unsafe` block,
+        // or if it calls something in an unsafe context.
+        let contains_unsafe = |node: graph::NodeIndex<RLIndex>| {
+            self[node].is_unsafe_fn()
+                || self[node].unsafe_blocks() > 0
+                || self.edges(node).any(|edge| edge.weight().unsafe_context())
+        };
+
+        let mut unsafe_surface = Vec::new();
+        for node in self.node_indices() {
+            if !self[node].is_public() || self[node].is_unsafe_fn() {
+                continue;
+            }
+            let mut reached = Vec::new();
+            let mut dfs = petgraph::visit::Dfs::new(self, node);
+            while let Some(next) = dfs.next(self) {
+                if contains_unsafe(next) {
+                    reached.push(RLIndex::from(next));
+                }
+            }
+            if !reached.is_empty() {
+                reached.sort();
+                unsafe_surface.push((RLIndex::from(node), reached));
+            }
+        }
+        unsafe_surface
+    }
+
+    fn entry_points(&self, include_public: bool) -> Vec<(Self::Index, EntryPointKind)> {
+        self.node_indices()
+            .filter_map(|node| {
+                self[node]
+                    .entry_point(include_public)
+                    .map(|entry_point| (RLIndex::from(node), entry_point))
+            })
+            .collect()
+    }
+
+    fn callers(&self, nodes: &[Self::Index]) -> Vec<Self::Index> {
+        let mut callers = FxHashSet::default();
+        let mut stack = nodes
+            .iter()
+            .copied()
+            .map(graph::NodeIndex::from)
+            .collect::<Vec<_>>();
+        while let Some(node) = stack.pop() {
+            if callers.insert(node) {
+                stack.extend(self.neighbors_directed(node, petgraph::Direction::Incoming));
+            }
+        }
+        let mut callers = callers.into_iter().map(RLIndex::from).collect::<Vec<_>>();
+        callers.sort();
+        callers
+    }
+
+    fn unreachable(&self, include_public: bool) -> Vec<Self::Index> {
+        let mut reachable = FxHashSet::default();
+        let mut stack = self
+            .node_indices()
+            .filter(|node| self[*node].entry_point(include_public).is_some())
+            .collect::<Vec<_>>();
+        while let Some(node) = stack.pop() {
+            if reachable.insert(node) {
+                stack.extend(self.neighbors(node));
+            }
+        }
+        self.node_indices()
+            .filter(|node| self[*node].is_local_fn() && !reachable.contains(node))
+            .map(RLIndex::from)
+            .collect()
+    }
+
+    fn rl_nodes(&self) -> Vec<(Self::Index, &Self::Node)> {
+        self.node_indices()
+            .map(|node| (RLIndex::from(node), &self[node]))
+            .collect()
+    }
+
+    fn rl_edges(&self) -> Vec<(Self::Index, Self::Index, &Self::Edge)> {
+        self.edge_references()
+            .map(|edge| {
+                (
+                    RLIndex::from(edge.source()),
+                    RLIndex::from(edge.target()),
+                    edge.weight(),
+                )
+            })
+            .collect()
+    }
+
+    fn condense(&self, group_of: &dyn Fn(&Self::Node) -> String) -> RLCondensedGraph {
+        let mut condensed = RLCondensedGraph::default();
+        let groups = self
+            .node_indices()
+            .map(|node| condensed.add_function(group_of(&self[node])))
+            .collect::<Vec<_>>();
+        for edge in self.edge_references() {
+            condensed.add_call(
+                groups[edge.source().index()],
+                groups[edge.target().index()],
+                edge.weight().ownership_score(),
+            );
+        }
+        condensed
+    }
+
     fn merge(&mut self, other: &Self) {
         for node in other.node_indices() {
             let node = other.node_weight(node).unwrap().clone();
-            if !self.node_indices().any(|n| self[n] == node) {
-                let _index = self.rl_add_node(node);
+            match self.node_indices().find(|n| self[*n] == node) {
+                Some(index) => self[index].merge(&node),
+                None => {
+                    let _index = self.rl_add_node(node);
+                }
             }
         }
 
@@ -85,16 +230,89 @@ impl RLGraph for graph::DiGraph<RLNode, RLEdge, RLIndex> {
                     Some(promoted) => format!("{:?}", promoted),
                     None => "None".to_string(),
                 };
-                format!("label=\"i{}: {} - {}\"", index, node.def_id_str(), promoted)
+                let closure = match node.closure_kind() {
+                    Some(closure_kind) => {
+                        let closure_captures = node
+                            .closure_captures()
+                            .iter()
+                            .map(|(place, capture_kind)| format!("{}: {:?}", place, capture_kind))
+                            .collect::<Vec<_>>();
+                        format!(" - {:?} [{}]", closure_kind, closure_captures.join(", "))
+                    }
+                    None => "".to_string(),
+                };
+                let unsafe_fn = match (node.is_unsafe_fn(), node.unsafe_blocks()) {
+                    (true, _) => " - unsafe fn".to_string(),
+                    (false, 0) => "".to_string(),
+                    (false, unsafe_blocks) => format!(" - unsafe blocks: {}", unsafe_blocks),
+                };
+                let runtime_borrows = match (node.runtime_borrows(), node.runtime_borrows_mut()) {
+                    (0, 0) => "".to_string(),
+                    (runtime_borrows, runtime_borrows_mut) => format!(
+                        " - runtime borrows: {} shared, {} mut",
+                        runtime_borrows, runtime_borrows_mut
+                    ),
+                };
+                let borrowck_facts = match node.borrowck_facts() {
+                    Some(borrowck_facts) => format!(
+                        " - borrowck: {} shared, {} mut, {} reborrows, {} two-phase, {} moves, {} outliving",
+                        borrowck_facts.shared_borrows(),
+                        borrowck_facts.mut_borrows(),
+                        borrowck_facts.reborrows(),
+                        borrowck_facts.two_phase_borrows(),
+                        borrowck_facts.moves(),
+                        borrowck_facts.outliving_borrows()
+                    ),
+                    None => "".to_string(),
+                };
+                let ownership_cost = match node.ownership_cost() {
+                    Some(ownership_cost) => format!(" - {}", ownership_cost),
+                    None => "".to_string(),
+                };
+                format!(
+                    "label=\"i{}: {} - {}{}{}{}{}{}\"",
+                    index,
+                    node.def_id_str(),
+                    promoted,
+                    closure,
+                    unsafe_fn,
+                    runtime_borrows,
+                    borrowck_facts,
+                    ownership_cost
+                )
                 // format!("label=\"i{}: {:?}\"", index, node.def_id())
             };
 
+        // The fields are formatted one by one, since the precision of `{:.2?}` would
+        // truncate the booleans (e.g., `false` to `fa`).
+        let get_edge_attr =
+            |_g: &&graph::DiGraph<RLNode, RLEdge, RLIndex>,
+             edge: graph::EdgeReference<'_, RLEdge, RLIndex>| {
+                let edge = edge.weight();
+                let label = format!(
+                    "RLEdge {{ call_multiplier: {:?}, arg_weights: {:.2?}, via: {:?}, drop: {:?}, call_source: {:?}, unsafe_context: {}, loop_depth: {}, cleanup: {}, conditional: {}, arg_tys: {:?}, span: {:?} }}",
+                    edge.call_kind(),
+                    edge.arg_weights(),
+                    edge.via(),
+                    edge.drop(),
+                    edge.call_source(),
+                    edge.unsafe_context(),
+                    edge.loop_depth(),
+                    edge.is_cleanup(),
+                    edge.is_conditional(),
+                    edge.arg_tys(),
+                    edge.span()
+                );
+                // The quotes (e.g., of the strings in the edge) must be escaped.
+                format!("label=\"{}\"", label.replace('"', "\\\""))
+            };
+
         format!(
             "{:?}",
             Dot::with_attr_getters(
                 &self,
                 &[Config::NodeNoLabel, Config::EdgeNoLabel],
-                &|_g, e| format!("label=\"{:.2?}\"", e.weight()),
+                &get_edge_attr,
                 &get_node_attr,
             )
         )
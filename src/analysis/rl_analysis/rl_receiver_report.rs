@@ -0,0 +1,259 @@
+use rustc_data_structures::fx::{FxHashMap, FxIndexMap};
+use rustc_hir::def::DefKind;
+use rustc_middle::ty::{self, TyCtxt};
+use rustc_span::{def_id::DefId, sym};
+
+use super::rl_context::{ArgUsageKind, CallKind};
+use super::rl_graph::{RLEdge, RLGraph, RLGraphNode, RLIndex, RLNode};
+
+/// How a method takes its receiver.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ReceiverKind {
+    /// `self`
+    Value,
+    /// `&self`
+    Ref,
+    /// `&mut self`
+    MutRef,
+    /// Any other receiver, e.g., `self: Box<Self>` or `self: Rc<Self>`.
+    Other,
+}
+
+impl std::fmt::Display for ReceiverKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ReceiverKind::Value => write!(f, "self"),
+            ReceiverKind::Ref => write!(f, "&self"),
+            ReceiverKind::MutRef => write!(f, "&mut self"),
+            ReceiverKind::Other => write!(f, "other"),
+        }
+    }
+}
+
+/// A method of an ADT with the number of its call sites in the crate, and the number
+/// of them whose receiver is the result of a `clone`.
+#[derive(Debug, Clone)]
+pub struct RLMethodReceiver {
+    method: String,
+    receiver_kind: ReceiverKind,
+    calls: usize,
+    cloned_calls: usize,
+}
+
+impl RLMethodReceiver {
+    /// A method consuming its receiver which is always called on a clone could borrow it.
+    pub fn is_always_cloned(&self) -> bool {
+        self.receiver_kind == ReceiverKind::Value
+            && self.calls > 0
+            && self.cloned_calls == self.calls
+    }
+}
+
+/// The report of the receivers of the inherent and trait methods of an ADT of the crate.
+#[derive(Debug, Clone)]
+pub struct RLReceiverReport {
+    /// The path of the ADT.
+    adt: String,
+    methods: Vec<RLMethodReceiver>,
+}
+
+impl std::fmt::Display for RLReceiverReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "`{}`", self.adt)?;
+        let mut methods = self.methods.iter().collect::<Vec<_>>();
+        methods.sort_by_key(|method| method.receiver_kind);
+        for group in methods.chunk_by(|a, b| a.receiver_kind == b.receiver_kind) {
+            let group_methods = group
+                .iter()
+                .map(|method| {
+                    format!(
+                        "`{}` (calls: {}, cloned: {})",
+                        method.method, method.calls, method.cloned_calls
+                    )
+                })
+                .collect::<Vec<_>>();
+            write!(
+                f,
+                "\n    {}: {}",
+                group[0].receiver_kind,
+                group_methods.join(", ")
+            )?;
+        }
+        for method in methods.iter().filter(|method| method.is_always_cloned()) {
+            write!(
+                f,
+                "\n    `{}` takes `self` but it is always called on a clone, consider `&self`",
+                method.method
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The number of calls of a method, and the number of them whose receiver is the result
+/// of a `clone`.
+#[derive(Debug, Copy, Clone, Default)]
+struct CallCount {
+    calls: usize,
+    cloned_calls: usize,
+}
+
+/// `RLReceiverReporter` groups the methods of each ADT of the crate by the kind of their
+/// receiver, and counts their calls in the graph, i.e., the edges reaching them, and the
+/// ones whose receiver is the result of a `clone` (`ArgUsageKind::Clone`).
+///
+/// The edges of the calls of a trait method reach the method of the trait, so they are
+/// counted for the method of the `impl` for the type of their receiver.
+/// The calls of `Clone::clone` have no edge, so they are counted from the ADTs cloned by
+/// the functions of the graph (see `RLNode::cloned_adts`).
+/// The default methods of the traits are not reported.
+pub struct RLReceiverReporter<'tcx, 'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    tcx: TyCtxt<'tcx>,
+    rl_graph: &'a G,
+}
+
+impl<'tcx, 'a, G> RLReceiverReporter<'tcx, 'a, G>
+where
+    G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+{
+    pub fn new(tcx: TyCtxt<'tcx>, rl_graph: &'a G) -> Self {
+        Self { tcx, rl_graph }
+    }
+
+    pub fn reports(&self) -> Vec<RLReceiverReport> {
+        let (call_counts, trait_call_counts) = self.call_counts();
+        let mut clone_counts: FxHashMap<DefId, usize> = FxHashMap::default();
+        for (_, node) in self.rl_graph.rl_nodes() {
+            for adt_def_id in node.cloned_adts() {
+                *clone_counts.entry(*adt_def_id).or_default() += 1;
+            }
+        }
+
+        let mut reports: FxIndexMap<DefId, RLReceiverReport> = FxIndexMap::default();
+        for (index, node) in self.rl_graph.rl_nodes() {
+            let def_id = node.def_id();
+            if node.is_statically_unknown() || node.promoted().is_some() || !def_id.is_local() {
+                continue;
+            }
+            let Some((adt_def_id, receiver_kind)) = self.method_receiver(def_id) else {
+                continue;
+            };
+            let (calls, cloned_calls) = if self.is_clone_method(def_id) {
+                (
+                    clone_counts.get(&adt_def_id).copied().unwrap_or_default(),
+                    0,
+                )
+            } else {
+                let call_count = call_counts.get(&index).copied().unwrap_or_default();
+                let trait_call_count = self
+                    .tcx
+                    .associated_item(def_id)
+                    .trait_item_def_id
+                    .and_then(|trait_item| trait_call_counts.get(&(trait_item, adt_def_id)))
+                    .copied()
+                    .unwrap_or_default();
+                (
+                    call_count.calls + trait_call_count.calls,
+                    call_count.cloned_calls + trait_call_count.cloned_calls,
+                )
+            };
+            reports
+                .entry(adt_def_id)
+                .or_insert_with(|| RLReceiverReport {
+                    adt: self.tcx.def_path_str(adt_def_id),
+                    methods: Vec::new(),
+                })
+                .methods
+                .push(RLMethodReceiver {
+                    method: self.tcx.def_path_str(def_id),
+                    receiver_kind,
+                    calls,
+                    cloned_calls,
+                });
+        }
+        reports.into_values().collect()
+    }
+
+    /// The ADT of the crate whose `impl` contains the method, and the kind of its receiver.
+    /// It is `None` if the `def_id` is not a method (i.e., it has no `self` parameter)
+    /// of an `impl` of an ADT of the crate.
+    fn method_receiver(&self, def_id: DefId) -> Option<(DefId, ReceiverKind)> {
+        if self.tcx.def_kind(def_id) != DefKind::AssocFn
+            || !self.tcx.associated_item(def_id).fn_has_self_parameter
+        {
+            return None;
+        }
+        let impl_def_id = self.tcx.impl_of_method(def_id)?;
+        let self_ty = self.tcx.type_of(impl_def_id).instantiate_identity();
+        let adt_def_id = match self_ty.kind() {
+            ty::TyKind::Adt(adt_def, _) if adt_def.did().is_local() => adt_def.did(),
+            _ => return None,
+        };
+
+        let receiver = self
+            .tcx
+            .fn_sig(def_id)
+            .instantiate_identity()
+            .skip_binder()
+            .inputs()[0];
+        let receiver_kind = match receiver.kind() {
+            _ if receiver == self_ty => ReceiverKind::Value,
+            ty::TyKind::Ref(_, ty, ty::Mutability::Not) if *ty == self_ty => ReceiverKind::Ref,
+            ty::TyKind::Ref(_, ty, ty::Mutability::Mut) if *ty == self_ty => ReceiverKind::MutRef,
+            _ => ReceiverKind::Other,
+        };
+        Some((adt_def_id, receiver_kind))
+    }
+
+    /// Whether the method is the `clone` of an `impl Clone`.
+    fn is_clone_method(&self, def_id: DefId) -> bool {
+        self.tcx.item_name(def_id) == sym::clone
+            && self
+                .tcx
+                .impl_of_method(def_id)
+                .and_then(|impl_def_id| self.tcx.trait_id_of_impl(impl_def_id))
+                == self.tcx.lang_items().clone_trait()
+    }
+
+    /// For each function of the graph, the number of its calls and the number of them
+    /// whose receiver (i.e., first argument) is the result of a `clone`.
+    /// The calls of the trait methods are counted by trait method and by ADT of the receiver.
+    fn call_counts(
+        &self,
+    ) -> (
+        FxHashMap<RLIndex, CallCount>,
+        FxHashMap<(DefId, DefId), CallCount>,
+    ) {
+        let mut call_counts: FxHashMap<RLIndex, CallCount> = FxHashMap::default();
+        let mut trait_call_counts: FxHashMap<(DefId, DefId), CallCount> = FxHashMap::default();
+        for (_, callee, edge) in self.rl_graph.rl_edges() {
+            if !matches!(edge.call_kind(), CallKind::Function | CallKind::Method) {
+                continue;
+            }
+            let callee_node = self.rl_graph.rl_node(callee);
+            let call_count = match edge.arg_tys().first().and_then(|receiver| receiver.adt()) {
+                Some(receiver_adt)
+                    if !callee_node.is_statically_unknown()
+                        && self.tcx.trait_of_item(callee_node.def_id()).is_some() =>
+                {
+                    trait_call_counts
+                        .entry((callee_node.def_id(), receiver_adt))
+                        .or_default()
+                }
+                _ => call_counts.entry(callee).or_default(),
+            };
+            call_count.calls += 1;
+            if edge
+                .arg_tys()
+                .first()
+                .is_some_and(|arg_ty| arg_ty.usage() == ArgUsageKind::Clone)
+            {
+                call_count.cloned_calls += 1;
+            }
+        }
+        (call_counts, trait_call_counts)
+    }
+}
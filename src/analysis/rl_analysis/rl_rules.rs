@@ -0,0 +1,170 @@
+use serde::Deserialize;
+
+use super::rl_calls::{matches_path, rules_from_file, RLCall};
+use super::rl_context::{CallKind, MutabilityKind, OperandKind, RLTyKind};
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+
+/// The severity of a finding.
+#[derive(Debug, Copy, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RLSeverity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl std::fmt::Display for RLSeverity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RLSeverity::Error => write!(f, "error"),
+            RLSeverity::Warning => write!(f, "warning"),
+            RLSeverity::Note => write!(f, "note"),
+        }
+    }
+}
+
+/// The user-defined rules over the edges of the graph, read from a TOML file with
+/// a `[[rule]]` table for each rule:
+/// ```toml
+/// [[rule]]
+/// severity = "error"
+/// message = "a composite value must not be moved into the metrics"
+/// callee = "app::metrics::*"
+/// arg = { operand = "Move", ty = "Composite" }
+///
+/// [[rule]]
+/// severity = "warning"
+/// message = "the core crate must not call the methods of other crates"
+/// caller_crate = "core_logic"
+/// callee_crate = "other_crate"
+/// call_kind = ["Method"]
+/// ```
+/// An edge matches a rule if it matches all the fields of the rule which are given:
+/// - `caller` and `callee`: the path of the caller and of the callee, matching as the
+///   patterns of the layering rules (e.g., `app::metrics` matches `app::metrics::record`);
+/// - `caller_crate` and `callee_crate`: the name of the crate of the caller and of the callee;
+/// - `call_kind`: one of the kinds of the edge (e.g., `Function`, `Method`, `Drop`);
+/// - `arg`: at least one argument of the call matches all the given fields of `operand`
+///   (`Move`, `Copy`, `Constant`), `mutability` (`Mut`, `Not`, `Interior`) and `ty`
+///   (`Primitive`, `Composite`, `Closure`, `Unknown`).
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct RLRules {
+    #[serde(default, rename = "rule")]
+    rules: Vec<RLRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RLRule {
+    severity: RLSeverity,
+    message: String,
+    caller: Option<String>,
+    callee: Option<String>,
+    caller_crate: Option<String>,
+    callee_crate: Option<String>,
+    #[serde(default)]
+    call_kind: Vec<CallKind>,
+    arg: Option<RLArgPattern>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RLArgPattern {
+    operand: Option<OperandKind>,
+    mutability: Option<MutabilityKind>,
+    ty: Option<RLTyKind>,
+}
+
+impl RLRules {
+    pub fn from_file(file_path: &str) -> Result<Self, String> {
+        rules_from_file(file_path)
+    }
+}
+
+impl RLRule {
+    fn matches(&self, call: &RLCall<'_>) -> bool {
+        let matches_pattern = |pattern: &Option<String>, path: &str| {
+            pattern
+                .as_ref()
+                .is_none_or(|pattern| matches_path(pattern, path))
+        };
+        let matches_crate =
+            |krate: &Option<String>, name: &str| krate.as_ref().is_none_or(|krate| krate == name);
+        matches_pattern(&self.caller, call.caller())
+            && matches_pattern(&self.callee, call.callee())
+            && matches_crate(&self.caller_crate, call.caller_crate())
+            && matches_crate(&self.callee_crate, call.callee_crate())
+            && (self.call_kind.is_empty() || self.call_kind.contains(call.edge().call_kind()))
+            && self.arg.as_ref().is_none_or(|arg| {
+                call.edge()
+                    .arg_weights()
+                    .iter()
+                    .any(|(operand, mutability, ty)| {
+                        arg.operand.as_ref().is_none_or(|o| o == operand)
+                            && arg.mutability.is_none_or(|m| m == *mutability)
+                            && arg.ty.is_none_or(|t| t == *ty)
+                    })
+            })
+    }
+}
+
+/// An edge matching a user-defined rule.
+#[derive(Debug, Clone)]
+pub struct RLFinding {
+    severity: RLSeverity,
+    message: String,
+    caller: String,
+    callee: String,
+    span: Option<String>,
+}
+
+impl std::fmt::Display for RLFinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {}: {} -> {} ({})",
+            self.severity,
+            self.message,
+            self.caller,
+            self.callee,
+            self.span.as_deref().unwrap_or("unknown span")
+        )
+    }
+}
+
+/// `RLRuleChecker` matches the edges of the graph against the user-defined rules.
+///
+/// As for the layering rules, each edge is in the graph of the crate of its caller,
+/// so the graph of each crate is checked.
+pub struct RLRuleChecker {
+    rules: RLRules,
+}
+
+impl RLRuleChecker {
+    pub fn new(rules: RLRules) -> Self {
+        Self { rules }
+    }
+
+    /// The edges matching a rule, in the order of the rules.
+    /// The statically unknown call sites are skipped, since their callee is unknown.
+    pub fn findings<G>(&self, rl_graph: &G) -> Vec<RLFinding>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let calls = RLCall::collect(rl_graph);
+
+        let mut findings = Vec::new();
+        for rule in &self.rules.rules {
+            for call in calls.iter().filter(|call| rule.matches(call)) {
+                findings.push(RLFinding {
+                    severity: rule.severity,
+                    message: rule.message.clone(),
+                    caller: call.caller().to_string(),
+                    callee: call.callee().to_string(),
+                    span: call.edge().span().map(str::to_string),
+                });
+            }
+        }
+        findings
+    }
+}
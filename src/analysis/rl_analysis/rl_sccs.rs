@@ -0,0 +1,54 @@
+use super::rl_graph::{RLEdge, RLGraph, RLIndex, RLNode};
+use super::rl_petgraph::call_graph;
+
+/// A non-trivial strongly connected component of the graph, i.e., a set of functions which
+/// are (mutually) recursive, with the calls `(caller, callee)` between its functions.
+/// A single function is a component only if it calls itself.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct RLScc {
+    functions: Vec<RLIndex>,
+    calls: Vec<(RLIndex, RLIndex)>,
+}
+
+impl RLScc {
+    /// The non-trivial strongly connected components of the graph, sorted by their functions.
+    pub fn collect<G>(rl_graph: &G) -> Vec<Self>
+    where
+        G: RLGraph<Node = RLNode, Edge = RLEdge, Index = RLIndex>,
+    {
+        let call_graph = call_graph(rl_graph);
+        let mut sccs = Vec::new();
+        for scc in petgraph::algo::tarjan_scc(&call_graph) {
+            let is_recursive = scc.len() > 1 || call_graph.contains_edge(scc[0], scc[0]);
+            if !is_recursive {
+                continue;
+            }
+            let mut functions = scc.iter().map(|node| call_graph[*node]).collect::<Vec<_>>();
+            functions.sort();
+            // The parallel calls (e.g., two calls to the same function) are reported once.
+            let mut calls = rl_graph
+                .rl_edges()
+                .into_iter()
+                .filter(|(caller, callee, _)| {
+                    functions.contains(caller) && functions.contains(callee)
+                })
+                .map(|(caller, callee, _)| (caller, callee))
+                .collect::<Vec<_>>();
+            calls.sort();
+            calls.dedup();
+            sccs.push(RLScc { functions, calls });
+        }
+        sccs.sort();
+        sccs
+    }
+
+    /// The functions of the component, sorted by index.
+    pub fn functions(&self) -> &[RLIndex] {
+        &self.functions
+    }
+
+    /// The calls between the functions of the component, sorted by `(caller, callee)`.
+    pub fn calls(&self) -> &[(RLIndex, RLIndex)] {
+        &self.calls
+    }
+}
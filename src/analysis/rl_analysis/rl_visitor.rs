@@ -1,18 +1,38 @@
 use crate::analysis::rl_analysis::rl_args_resolver::RLArgsResolver;
+use crate::analysis::rl_analysis::rl_borrow_suggester::RLReadOnlyParam;
+use crate::analysis::rl_analysis::rl_borrowck::RLBorrowckFacts;
 use crate::analysis::rl_analysis::rl_call_resolver::RLCallResolver;
+use crate::analysis::rl_analysis::rl_condensed::{def_path, impl_path, module_of};
+use crate::analysis::rl_analysis::rl_context::CallSourceKind;
+use crate::analysis::rl_analysis::rl_context::CaptureKind;
+use crate::analysis::rl_analysis::rl_context::ClosureKind;
 use crate::analysis::rl_analysis::rl_context::ComeFromSwitchCache;
+use crate::analysis::rl_analysis::rl_context::EntryPointKind;
 use crate::analysis::rl_analysis::rl_context::MutabilityKind;
+use crate::analysis::rl_analysis::rl_context::RLArgTy;
+use crate::analysis::rl_analysis::rl_context::RLDefSpan;
+use crate::analysis::rl_analysis::rl_context::RLDrop;
+use crate::analysis::rl_analysis::rl_context::RLFnSig;
 use crate::analysis::rl_analysis::rl_context::RLTy;
 use crate::analysis::rl_analysis::rl_context::RLValue;
+use crate::analysis::rl_analysis::rl_control_flow::RLControlFlow;
+use crate::analysis::rl_analysis::rl_entry_points::is_local_fn;
+use crate::analysis::rl_analysis::rl_ownership::RLOwnershipFlow;
 use crate::analysis::utils::TextMod;
 
+use rustc_const_eval::interpret::GlobalAlloc;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
+use rustc_hir as hir;
+use rustc_hir::intravisit;
+use rustc_hir::intravisit::Visitor as _;
+use rustc_middle::hir::nested_filter;
 use rustc_middle::mir;
 use rustc_middle::mir::visit::Visitor;
 use rustc_middle::mir::Promoted;
 use rustc_middle::mir::Rvalue;
 use rustc_middle::ty;
+use rustc_middle::ty::TypeFoldable;
 use rustc_span::def_id::DefId;
 use rustc_span::def_id::LocalDefId;
 use rustc_span::source_map::Spanned;
@@ -59,13 +79,107 @@ where
         self.rl_graph.clone()
     }
 
+    /// Attach the borrow checker facts to the node of the `local_def_id`.
+    pub fn set_borrowck_facts(&mut self, local_def_id: LocalDefId, facts: RLBorrowckFacts) {
+        let index = self.add_node_if_needed((local_def_id.to_def_id(), None));
+        self.rl_graph.rl_node_mut(index).set_borrowck_facts(facts);
+    }
+
+    /// Attach the ownership facts to the node of the `local_def_id`.
+    pub fn set_ownership_flow(&mut self, local_def_id: LocalDefId, flow: RLOwnershipFlow) {
+        let index = self.add_node_if_needed((local_def_id.to_def_id(), None));
+        self.rl_graph.rl_node_mut(index).set_ownership_flow(flow);
+    }
+
+    /// Attach the read-only parameters to the node of the `local_def_id`.
+    pub fn set_read_only_params(&mut self, local_def_id: LocalDefId, params: Vec<RLReadOnlyParam>) {
+        let index = self.add_node_if_needed((local_def_id.to_def_id(), None));
+        self.rl_graph
+            .rl_node_mut(index)
+            .set_read_only_params(params);
+    }
+
+    /// Mark the node of the `local_def_id` as an entry point of the graph.
+    pub fn set_entry_point(&mut self, local_def_id: LocalDefId, entry_point: EntryPointKind) {
+        let index = self.add_node_if_needed((local_def_id.to_def_id(), None));
+        self.rl_graph
+            .rl_node_mut(index)
+            .set_entry_point(entry_point);
+    }
+
     /// The entry point of the visitor.
     /// It visits the local_def_id and the body of the function.
     pub fn visit_local_def_id(&mut self, local_def_id: LocalDefId, body: &'a mir::Body<'tcx>) {
-        let _ = self.add_node_if_needed((local_def_id.to_def_id(), None));
+        let index = self.add_node_if_needed((local_def_id.to_def_id(), None));
+
+        if let ty::TyKind::Closure(_, closure_args) = self
+            .analyzer
+            .tcx
+            .type_of(local_def_id)
+            .instantiate_identity()
+            .kind()
+        {
+            let closure_kind = ClosureKind::from(closure_args.as_closure().kind());
+            let closure_captures = self
+                .analyzer
+                .tcx
+                .closure_captures(local_def_id)
+                .iter()
+                .map(|captured_place| {
+                    (
+                        captured_place.to_string(self.analyzer.tcx),
+                        CaptureKind::from(captured_place.info.capture_kind),
+                    )
+                })
+                .collect::<Vec<_>>();
+            self.rl_graph
+                .rl_node_mut(index)
+                .set_closure_info(closure_kind, closure_captures);
+        }
+
+        // The lines of the definition are needed to map the changed lines of a diff
+        // onto the functions.
+        let span = self
+            .analyzer
+            .tcx
+            .hir()
+            .span_with_body(self.analyzer.tcx.local_def_id_to_hir_id(local_def_id));
+        if let (Some(file), first_line, _, last_line, _) = self
+            .analyzer
+            .tcx
+            .sess
+            .source_map()
+            .span_to_location_info(span)
+        {
+            let file = file.name.prefer_remapped_unconditionaly().to_string();
+            self.rl_graph
+                .rl_node_mut(index)
+                .set_def_span(RLDefSpan::new(file, first_line, last_line));
+        }
 
         self.ctx.current_local_def_id = Some(local_def_id.to_def_id());
 
+        self.ctx.current_body = Some(body);
+        self.ctx.current_control_flow = Some(RLControlFlow::new(body));
+
+        let tcx = self.analyzer.tcx;
+        let typeck_root_def_id = tcx.typeck_root_def_id(local_def_id.to_def_id());
+        self.ctx.current_in_unsafe_fn = Self::is_unsafe_fn(tcx, typeck_root_def_id);
+        // The unsafe blocks of the function enclosing a closure are needed too,
+        // since a closure defined in an `unsafe` block is in an unsafe context.
+        self.ctx.current_unsafe_blocks =
+            UnsafeBlockCollector::collect(tcx, typeck_root_def_id.expect_local())
+                .into_iter()
+                .map(|(span, _)| span)
+                .collect();
+        let unsafe_blocks = UnsafeBlockCollector::collect(tcx, local_def_id)
+            .into_iter()
+            .filter(|(_, closure_depth)| *closure_depth == 0)
+            .count();
+        self.rl_graph
+            .rl_node_mut(index)
+            .set_unsafe_blocks(unsafe_blocks);
+
         self.ctx.set_function_args = body.args_iter().collect::<FxHashSet<_>>();
 
         for (local, local_decl) in body.local_decls.iter_enumerated() {
@@ -75,7 +189,7 @@ where
             // It ensures that the local variable is in the map with the corresponding type.
             let ty = RLTy::new(
                 local_decl.ty.kind(),
-                MutabilityKind::from(local_decl.mutability),
+                self.mutability_of_local(local_decl),
                 // match local_decl.local_info.as_ref() {
                 //     mir::ClearCrossCrate::Set(v) => match v.as_ref() {
                 //         mir::LocalInfo::User(binding_form) => Some(binding_form.clone()),
@@ -118,6 +232,192 @@ where
 
         // Clear current_local_def_id
         self.ctx.current_local_def_id = None;
+
+        // Clear current_body
+        self.ctx.current_body = None;
+
+        // Clear current_control_flow
+        self.ctx.current_control_flow = None;
+
+        // Clear map_static_pointer and set_static_accesses
+        self.ctx.map_static_pointer = FxHashMap::default();
+        self.ctx.set_static_accesses = FxHashSet::default();
+
+        // Clear current_unsafe_blocks and current_in_unsafe_fn
+        self.ctx.current_unsafe_blocks = Vec::new();
+        self.ctx.current_in_unsafe_fn = false;
+    }
+
+    /// Link each statically unknown call site to every function and closure whose
+    /// address is taken in the crate and whose signature matches the one of the call site.
+    ///
+    /// It must be called after visiting all the bodies of the crate, since the address
+    /// of a function can be taken after (in visiting order) the call site.
+    pub fn link_unknown_call_sites(&mut self) {
+        let call_sites = self
+            .ctx
+            .map_unknown_call_site
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for (unknown_index, unknown_fn_sig) in call_sites {
+            let candidates = self
+                .ctx
+                .map_address_taken
+                .iter()
+                .filter(|(_, (_, fn_sig))| unknown_fn_sig.matches(fn_sig))
+                .map(|(def_id, (call_kind, _))| (*def_id, call_kind.clone()))
+                .collect::<Vec<_>>();
+            for (def_id, call_kind) in candidates {
+                log::debug!(
+                    "Linking the statically unknown call site ({:?}) to the {:?} ({:?})",
+                    unknown_index,
+                    call_kind,
+                    def_id
+                );
+                let candidate_index = self.add_node_if_needed((def_id, None));
+                let edge = RLEdge::create((CallKind::OverApproximated, Vec::new()));
+                self.rl_graph
+                    .rl_add_edge(unknown_index, candidate_index, edge);
+            }
+        }
+    }
+
+    /// Compute the signature of the callee of a call terminator.
+    ///
+    /// In case of a function pointer, the inputs are the types of the arguments.
+    ///
+    /// In case of a call through the `Fn*` traits (e.g., `<F as Fn<(T,)>>::call(move _1, move _2)`)
+    /// the first argument is the callee itself and the second one is the tuple of the arguments.
+    ///
+    /// The output is always the type of the destination of the call.
+    fn call_fn_sig(
+        &self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        destination: &mir::Place<'tcx>,
+    ) -> RLFnSig<'tcx> {
+        let tcx = self.analyzer.tcx;
+        let body = self.ctx.current_body.unwrap();
+        let inputs = match func.ty(body, tcx).kind() {
+            ty::TyKind::FnDef(_, _) => match args.get(1) {
+                Some(arg) => match arg.node.ty(body, tcx).kind() {
+                    ty::TyKind::Tuple(tys) => tys.to_vec(),
+                    _ => vec![arg.node.ty(body, tcx)],
+                },
+                None => Vec::new(),
+            },
+            _ => args.iter().map(|arg| arg.node.ty(body, tcx)).collect(),
+        };
+        let output = destination.ty(body, tcx).ty;
+        RLFnSig::new(
+            inputs
+                .into_iter()
+                .map(|input| tcx.erase_regions(input))
+                .collect(),
+            tcx.erase_regions(output),
+        )
+    }
+
+    /// Compute the signature of a function item.
+    /// It returns `None` if the `def_id` is not a function.
+    fn fn_item_sig(&self, def_id: DefId) -> Option<RLFnSig<'tcx>> {
+        let tcx = self.analyzer.tcx;
+        if !matches!(
+            tcx.def_kind(def_id),
+            rustc_hir::def::DefKind::Fn | rustc_hir::def::DefKind::AssocFn
+        ) {
+            return None;
+        }
+        let fn_sig =
+            tcx.instantiate_bound_regions_with_erased(tcx.fn_sig(def_id).instantiate_identity());
+        Some(RLFnSig::new(
+            fn_sig
+                .inputs()
+                .iter()
+                .map(|input| tcx.erase_regions(*input))
+                .collect(),
+            tcx.erase_regions(fn_sig.output()),
+        ))
+    }
+
+    /// Compute the signature of a closure, untupling its inputs.
+    fn closure_sig(&self, closure_args: ty::GenericArgsRef<'tcx>) -> RLFnSig<'tcx> {
+        let tcx = self.analyzer.tcx;
+        let fn_sig = tcx.instantiate_bound_regions_with_erased(closure_args.as_closure().sig());
+        let inputs = match fn_sig.inputs().first().map(|input| input.kind()) {
+            Some(ty::TyKind::Tuple(tys)) => tys.to_vec(),
+            _ => fn_sig.inputs().to_vec(),
+        };
+        RLFnSig::new(
+            inputs
+                .into_iter()
+                .map(|input| tcx.erase_regions(input))
+                .collect(),
+            tcx.erase_regions(fn_sig.output()),
+        )
+    }
+
+    /// Record the functions and closures whose address is taken by the `rvalue`.
+    ///
+    /// For instance, in the following MIR:
+    /// ```rust,ignore
+    /// _1 = test as fn(T) (PointerCoercion(ReifyFnPointer, AsCast));
+    /// _2 = {closure@src/main.rs:18:18: 18:20};
+    /// ```
+    /// both `test` and the closure are recorded.
+    fn record_address_taken_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>) {
+        match rvalue {
+            mir::Rvalue::Cast(
+                mir::CastKind::PointerCoercion(
+                    ty::adjustment::PointerCoercion::ReifyFnPointer
+                    | ty::adjustment::PointerCoercion::ClosureFnPointer(_),
+                    _,
+                ),
+                operand,
+                _,
+            )
+            | mir::Rvalue::Use(operand) => self.record_address_taken_operand(operand),
+            mir::Rvalue::Aggregate(aggregate_kind, _) => {
+                if let mir::AggregateKind::Closure(def_id, closure_args) = **aggregate_kind {
+                    let fn_sig = self.closure_sig(closure_args);
+                    self.ctx
+                        .map_address_taken
+                        .insert(def_id, (CallKind::Closure, fn_sig));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record the function or the closure whose address is taken by the `operand`,
+    /// if it is a constant function item or a constant (zero-sized) closure.
+    ///
+    /// For instance, in the following MIR:
+    /// ```rust,ignore
+    /// _1 = outline::<fn(T) {test}>(const test) -> [return: bb1, unwind continue];
+    /// _2 = outline::<{closure@src/main.rs:6:13: 6:15}>(const ZeroSized: {closure@src/main.rs:6:13: 6:15}) -> [return: bb2, unwind continue];
+    /// ```
+    /// both `test` and the closure are recorded.
+    fn record_address_taken_operand(&mut self, operand: &mir::Operand<'tcx>) {
+        if let mir::Operand::Constant(const_operand) = operand {
+            match const_operand.ty().kind() {
+                ty::TyKind::FnDef(def_id, _) => {
+                    if let Some(fn_sig) = self.fn_item_sig(*def_id) {
+                        self.ctx
+                            .map_address_taken
+                            .insert(*def_id, (CallKind::Function, fn_sig));
+                    }
+                }
+                ty::TyKind::Closure(def_id, closure_args) => {
+                    let fn_sig = self.closure_sig(closure_args);
+                    self.ctx
+                        .map_address_taken
+                        .insert(*def_id, (CallKind::Closure, fn_sig));
+                }
+                _ => {}
+            }
+        }
     }
 
     /// Update the arguments of the function call.
@@ -192,10 +492,47 @@ where
                 args
             }
             CallKind::Clone => unreachable!(),
+            CallKind::Indirect => unreachable!(),
+            CallKind::Drop => unreachable!(),
+            CallKind::OverApproximated => unreachable!(),
+            CallKind::Reads => unreachable!(),
+            CallKind::Writes => unreachable!(),
             CallKind::Unknown => unreachable!(),
         }
     }
 
+    /// Return `true` if `def_id` is a function (or a method) declared as `unsafe fn`.
+    fn is_unsafe_fn(tcx: ty::TyCtxt<'tcx>, def_id: DefId) -> bool {
+        matches!(
+            tcx.def_kind(def_id),
+            rustc_hir::def::DefKind::Fn | rustc_hir::def::DefKind::AssocFn
+        ) && tcx.fn_sig(def_id).skip_binder().safety().is_unsafe()
+    }
+
+    /// Return `true` if the code at `span` (in the visited function) is in an unsafe context,
+    /// i.e., inside an `unsafe` block or in the body of an `unsafe fn`.
+    ///
+    /// The span is walked up to its outermost call site, so that a call generated
+    /// by a macro invoked inside an `unsafe` block is in an unsafe context too.
+    fn is_in_unsafe_context(&self, span: rustc_span::Span) -> bool {
+        let span = span.source_callsite();
+        self.ctx.current_in_unsafe_fn
+            || self
+                .ctx
+                .current_unsafe_blocks
+                .iter()
+                .any(|unsafe_block| unsafe_block.contains(span))
+    }
+
+    /// Return `true` if the calls of the class `call_source` must not be added to the graph.
+    fn is_call_source_hidden(&self, call_source: CallSourceKind) -> bool {
+        self.analyzer
+            .cli_args
+            .hide_call_source
+            .iter()
+            .any(|&hidden| CallSourceKind::from(hidden) == call_source)
+    }
+
     /// Add an edge between the current visited function and the function that is called.
     /// The edge is weighted by the arguments of the function call.
     /// The `to_def_id` is the def_id of the function that is called.
@@ -204,6 +541,9 @@ where
         &mut self,
         to_def_id: (DefId, Option<Promoted>),
         arg_weights: (CallKind, Vec<(OperandKind, MutabilityKind, RLTyKind)>),
+        arg_tys: Vec<RLArgTy>,
+        call_source: CallSourceKind,
+        unsafe_context: bool,
     ) {
         log::debug!(
             "Adding an edge between the current visited function ({:?}) and the function that is called ({:?}) with the arguments: {:?}",
@@ -214,7 +554,476 @@ where
         let fun_caller =
             self.ctx.rl_graph_index_map[&(self.ctx.current_local_def_id.unwrap(), None)];
         let fun_callee = self.add_node_if_needed(to_def_id);
-        let edge = RLEdge::create(arg_weights);
+        let mut edge = RLEdge::create(arg_weights);
+        edge.set_arg_tys(arg_tys);
+        edge.set_call_source(call_source);
+        edge.set_unsafe_context(unsafe_context);
+        self.add_edge_in_current_basic_block(fun_caller, fun_callee, edge);
+    }
+
+    /// Add an `Indirect` edge between the current visited function and each closure
+    /// (or function item) passed as argument to the non-local function `callee`.
+    ///
+    /// For instance, in the following MIR:
+    /// ```rust,ignore
+    /// _3 = <std::slice::Iter<'_, i32> as Iterator>::map::<i32, {closure@src/main.rs:3:22: 3:25}>(move _4, const ZeroSized: {closure@src/main.rs:3:22: 3:25}) -> [return: bb2, unwind continue];
+    /// ```
+    /// the closure is actually called by `Iterator::map`, whose body is not analyzed,
+    /// so without this edge the closure would not have any incoming edge.
+    /// The edge is tagged with the path of the `callee` (e.g., `std::iter::Iterator::map`)
+    /// and with the `call_source` and the `unsafe_context` of the call to the `callee`.
+    fn add_indirect_edges(
+        &mut self,
+        callee: DefId,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        call_source: CallSourceKind,
+        unsafe_context: bool,
+    ) {
+        let tcx = self.analyzer.tcx;
+        let body = self.ctx.current_body.unwrap();
+        for arg in args {
+            let def_id = match arg.node.ty(body, tcx).peel_refs().kind() {
+                ty::TyKind::Closure(def_id, _) | ty::TyKind::FnDef(def_id, _) => *def_id,
+                _ => continue,
+            };
+            let arg_weights = RLArgsResolver::new(&self.ctx)
+                .resolve(&CallKind::Indirect, std::slice::from_ref(&arg.node));
+            log::debug!(
+                "Adding an indirect edge between the current visited function ({:?}) and the {:?} passed to {:?}",
+                self.ctx.current_local_def_id.unwrap(),
+                def_id,
+                callee
+            );
+            let fun_caller =
+                self.ctx.rl_graph_index_map[&(self.ctx.current_local_def_id.unwrap(), None)];
+            let fun_callee = self.add_node_if_needed((def_id, None));
+            let mut edge = RLEdge::create(arg_weights);
+            edge.set_via(tcx.def_path_str(callee));
+            edge.set_call_source(call_source);
+            edge.set_unsafe_context(unsafe_context);
+            self.add_edge_in_current_basic_block(fun_caller, fun_callee, edge);
+        }
+    }
+
+    /// Add a `Drop` edge between the current visited function and each `Drop::drop`
+    /// implementation which is reached when the `place` is dropped.
+    ///
+    /// For instance, in the following MIR:
+    /// ```rust,ignore
+    /// bb1: {
+    ///     drop(_1) -> [return: bb2, unwind continue];
+    /// }
+    /// ```
+    /// if the type of `_1` is `Vec<T>` and `T` implements `Drop`, two edges are added:
+    /// one to `<Vec<T> as Drop>::drop` and one to `<T as Drop>::drop`.
+    ///
+    /// The edge records the dropped type. As for the other calls, the control-flow context
+    /// of the block tells whether the drop is on an unwind (cleanup) path and whether it is
+    /// conditional (e.g., a drop guarded by a drop flag).
+    fn add_drop_edges(&mut self, place: &mir::Place<'tcx>) {
+        if self.is_call_source_hidden(CallSourceKind::Desugaring) {
+            return;
+        }
+        let tcx = self.analyzer.tcx;
+        let body = self.ctx.current_body.unwrap();
+        let ty = place.ty(body, tcx).ty;
+
+        let mut destructors = Vec::new();
+        self.collect_destructors(ty, &mut FxHashSet::default(), &mut destructors);
+        if destructors.is_empty() {
+            return;
+        }
+
+        for destructor in destructors {
+            let arg_weights = RLArgsResolver::new(&self.ctx)
+                .resolve(&CallKind::Drop, &[mir::Operand::Move(*place)]);
+            log::debug!(
+                "Adding a drop edge between the current visited function ({:?}) and the destructor ({:?}) of {:?}",
+                self.ctx.current_local_def_id.unwrap(),
+                destructor,
+                ty
+            );
+            let fun_caller =
+                self.ctx.rl_graph_index_map[&(self.ctx.current_local_def_id.unwrap(), None)];
+            let fun_callee = self.add_node_if_needed((destructor, None));
+            let mut edge = RLEdge::create(arg_weights);
+            edge.set_drop(RLDrop::new(ty.to_string()));
+            edge.set_call_source(CallSourceKind::Desugaring);
+            self.add_edge_in_current_basic_block(fun_caller, fun_callee, edge);
+        }
+    }
+
+    /// Collect the `Drop::drop` implementations reached by the drop glue of `ty`.
+    ///
+    /// Only the values owned by `ty` are visited, since the others are not dropped with it:
+    /// - the fields of the local ADTs, since their drop glue drops them;
+    /// - the types which the non-local ADTs may drop according to the drop checker
+    ///   (e.g., the `T` of a `Vec<T>`), instead of their fields (e.g., the `RawVec` inside
+    ///   a `Vec`). A `MutexGuard<T>` or a `Ref<T>` only borrows its `T`, so it is not visited.
+    fn collect_destructors(
+        &self,
+        ty: ty::Ty<'tcx>,
+        visited: &mut FxHashSet<ty::Ty<'tcx>>,
+        destructors: &mut Vec<DefId>,
+    ) {
+        let tcx = self.analyzer.tcx;
+        let typing_env = ty::TypingEnv::post_analysis(tcx, self.ctx.current_local_def_id.unwrap());
+        if !visited.insert(ty) || !ty.needs_drop(tcx, typing_env) {
+            return;
+        }
+        match ty.kind() {
+            ty::TyKind::Adt(adt_def, generic_args) => {
+                if adt_def.is_manually_drop() || adt_def.is_phantom_data() {
+                    return;
+                }
+                if let Some(destructor) = tcx.adt_destructor(adt_def.did()) {
+                    destructors.push(destructor.did);
+                }
+                if adt_def.did().is_local() {
+                    for field in adt_def.all_fields() {
+                        self.collect_destructors(field.ty(tcx, generic_args), visited, destructors);
+                    }
+                } else {
+                    let constraint = tcx.adt_dtorck_constraint(adt_def.did());
+                    for dtorck_ty in constraint.dtorck_types.iter() {
+                        let dtorck_ty =
+                            ty::EarlyBinder::bind(*dtorck_ty).instantiate(tcx, generic_args);
+                        self.collect_destructors(dtorck_ty, visited, destructors);
+                    }
+                }
+            }
+            ty::TyKind::Tuple(tys) => {
+                for ty in tys.iter() {
+                    self.collect_destructors(ty, visited, destructors);
+                }
+            }
+            ty::TyKind::Array(ty, _) | ty::TyKind::Slice(ty) => {
+                self.collect_destructors(*ty, visited, destructors);
+            }
+            ty::TyKind::Closure(_, closure_args) => {
+                for ty in closure_args.as_closure().upvar_tys() {
+                    self.collect_destructors(ty, visited, destructors);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Add an edge between the current visited function and the statically unknown
+    /// call site at `location`.
+    /// Each call site has its own node, labelled with the signature of the callee,
+    /// so that unknown calls of different functions (and crates) are never merged.
+    fn add_statically_unknown_edge(
+        &mut self,
+        location: mir::Location,
+        fn_sig: RLFnSig<'tcx>,
+        arg_weights: (CallKind, Vec<(OperandKind, MutabilityKind, RLTyKind)>),
+        arg_tys: Vec<RLArgTy>,
+        call_source: CallSourceKind,
+        unsafe_context: bool,
+    ) {
+        let current_def_id = self.ctx.current_local_def_id.unwrap();
+        let fun_caller = self.ctx.rl_graph_index_map[&(current_def_id, None)];
+        let fun_callee = match self
+            .ctx
+            .map_unknown_call_site
+            .get(&(current_def_id, location))
+        {
+            Some((index, _)) => *index,
+            None => {
+                let call_site = format!(
+                    "{}@{:?}",
+                    self.analyzer.tcx.def_path_debug_str(current_def_id),
+                    location
+                );
+                let node = RLNode::create_statically_unknown(
+                    current_def_id,
+                    call_site,
+                    fn_sig.to_string(),
+                );
+                let index = self.rl_graph.rl_add_node(node);
+                self.ctx
+                    .map_unknown_call_site
+                    .insert((current_def_id, location), (index, fn_sig));
+                index
+            }
+        };
+        log::debug!(
+            "Adding an edge between the current visited function ({:?}) and the statically unknown call site ({:?}) with the arguments: {:?}",
+            current_def_id,
+            location,
+            arg_weights
+        );
+        let mut edge = RLEdge::create(arg_weights);
+        edge.set_arg_tys(arg_tys);
+        edge.set_call_source(call_source);
+        edge.set_unsafe_context(unsafe_context);
+        self.add_edge_in_current_basic_block(fun_caller, fun_callee, edge);
+    }
+
+    /// Return the static (or the thread local) pointed by the value of `rvalue`, if any.
+    ///
+    /// The statics are accessed through a pointer to their allocation
+    /// (e.g., `const {alloc1: &u32}`), the `#[thread_local]` statics through
+    /// a `ThreadLocalRef`, and the `thread_local!` through a constant `LocalKey`
+    /// (possibly promoted, e.g., `const enter::promoted[0]`).
+    /// The pointers copied, moved or reborrowed from another pointer to a static
+    /// point to the same static.
+    fn static_pointed_by(&self, rvalue: &mir::Rvalue<'tcx>) -> Option<DefId> {
+        let tcx = self.analyzer.tcx;
+        match rvalue {
+            mir::Rvalue::ThreadLocalRef(def_id) => Some(*def_id),
+            mir::Rvalue::Use(mir::Operand::Copy(place) | mir::Operand::Move(place))
+                if place.projection.is_empty() =>
+            {
+                self.ctx.map_static_pointer.get(&place.local).copied()
+            }
+            mir::Rvalue::Ref(_, _, place)
+            | mir::Rvalue::RawPtr(_, place)
+            | mir::Rvalue::CopyForDeref(place)
+                if place.is_indirect_first_projection() =>
+            {
+                self.ctx.map_static_pointer.get(&place.local).copied()
+            }
+            mir::Rvalue::Use(mir::Operand::Constant(const_operand)) => match const_operand.const_ {
+                mir::Const::Val(
+                    mir::ConstValue::Scalar(mir::interpret::Scalar::Ptr(pointer, _)),
+                    _,
+                ) => match tcx.global_alloc(pointer.provenance.alloc_id()) {
+                    GlobalAlloc::Static(def_id) => Some(def_id),
+                    _ => None,
+                },
+                mir::Const::Unevaluated(unevaluated_const, ty) if Self::is_local_key(tcx, ty) => {
+                    match unevaluated_const.promoted {
+                        None => Some(unevaluated_const.def),
+                        Some(promoted) => tcx.promoted_mir(unevaluated_const.def)[promoted]
+                            .basic_blocks
+                            .iter()
+                            .flat_map(|data| data.statements.iter())
+                            .find_map(|statement| match &statement.kind {
+                                mir::StatementKind::Assign(assign) => {
+                                    self.static_pointed_by(&assign.1)
+                                }
+                                _ => None,
+                            }),
+                    }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Return the mutability of a local.
+    ///
+    /// An immutable local is `MutabilityKind::Interior` if its type (or the type it refers to)
+    /// is not `Freeze`, i.e., it contains an `UnsafeCell`. The generic parameters are replaced
+    /// by `()`, so that a `&RefCell<T>` is `Interior` but a `&T` is not.
+    fn mutability_of_local(&self, local_decl: &mir::LocalDecl<'tcx>) -> MutabilityKind {
+        let tcx = self.analyzer.tcx;
+        if local_decl.mutability == ty::Mutability::Mut {
+            return MutabilityKind::Mut;
+        }
+        let ty = tcx.erase_regions(local_decl.ty.peel_refs());
+        if matches!(ty.kind(), ty::TyKind::Dynamic(..) | ty::TyKind::Foreign(_)) {
+            return MutabilityKind::Not;
+        }
+        let ty = ty.fold_with(&mut ty::fold::BottomUpFolder {
+            tcx,
+            ty_op: |ty| match ty.kind() {
+                ty::TyKind::Param(_) => tcx.types.unit,
+                _ => ty,
+            },
+            lt_op: |region| region,
+            ct_op: |ct| ct,
+        });
+        if ty.is_freeze(tcx, ty::TypingEnv::fully_monomorphized()) {
+            MutabilityKind::Not
+        } else {
+            MutabilityKind::Interior
+        }
+    }
+
+    /// Return the mode of the runtime borrow performed by the callee `func`, if any:
+    /// `MutabilityKind::Not` for a shared borrow (e.g., `RefCell::borrow`, `RwLock::read`),
+    /// `MutabilityKind::Mut` for an exclusive one (e.g., `RefCell::borrow_mut`, `Mutex::lock`).
+    fn runtime_borrow_of(&self, func: &mir::Operand<'tcx>) -> Option<MutabilityKind> {
+        let tcx = self.analyzer.tcx;
+        let (def_id, _) = func.const_fn_def()?;
+        let impl_def_id = tcx.impl_of_method(def_id)?;
+        let ty::TyKind::Adt(adt_def, _) = tcx.type_of(impl_def_id).instantiate_identity().kind()
+        else {
+            return None;
+        };
+        match (
+            tcx.def_path_str(adt_def.did()).as_str(),
+            tcx.item_name(def_id).as_str(),
+        ) {
+            ("std::cell::RefCell", "borrow" | "try_borrow")
+            | ("std::sync::RwLock", "read" | "try_read") => Some(MutabilityKind::Not),
+            ("std::cell::RefCell", "borrow_mut" | "try_borrow_mut")
+            | ("std::sync::Mutex", "lock" | "try_lock")
+            | ("std::sync::RwLock", "write" | "try_write") => Some(MutabilityKind::Mut),
+            _ => None,
+        }
+    }
+
+    /// Return `true` if `ty` is (a reference to) a `std::thread::LocalKey`,
+    /// i.e., the type of the keys declared by `thread_local!`.
+    fn is_local_key(tcx: ty::TyCtxt<'tcx>, ty: ty::Ty<'tcx>) -> bool {
+        match ty.peel_refs().kind() {
+            ty::TyKind::Adt(adt_def, _) => {
+                tcx.def_path_str(adt_def.did()) == "std::thread::LocalKey"
+            }
+            _ => false,
+        }
+    }
+
+    /// Record that the local assigned in `place` points to a static, if the `rvalue` does.
+    fn record_static_pointer(&mut self, place: &mir::Place<'tcx>, rvalue: &mir::Rvalue<'tcx>) {
+        let Some(local) = place.as_local() else {
+            return;
+        };
+        match self.static_pointed_by(rvalue) {
+            Some(def_id) => {
+                self.ctx.map_static_pointer.insert(local, def_id);
+            }
+            None => {
+                self.ctx.map_static_pointer.remove(&local);
+            }
+        }
+    }
+
+    /// Add a `Reads` (or `Writes`) edge if `place` dereferences a pointer to a static,
+    /// e.g., `_0 = copy (*_1)` reads and `(*_1) = move _2` writes the static pointed by `_1`.
+    fn record_static_deref(
+        &mut self,
+        place: &mir::Place<'tcx>,
+        context: mir::visit::PlaceContext,
+        location: mir::Location,
+    ) {
+        if !place.is_indirect_first_projection() || !context.is_use() {
+            return;
+        }
+        if let Some(def_id) = self.ctx.map_static_pointer.get(&place.local).copied() {
+            let call_kind = if context.is_mutating_use() {
+                CallKind::Writes
+            } else {
+                CallKind::Reads
+            };
+            self.add_static_access_edge(def_id, call_kind, location);
+        }
+    }
+
+    /// Add a `Reads` (or `Writes`) edge for each pointer to a static passed to a call.
+    ///
+    /// A pointer to a static is a write if it is mutable (e.g., `&mut T` or `*mut T`),
+    /// otherwise it is a read (e.g., `AtomicU32::fetch_add(&HITS, ..)`).
+    /// A `thread_local!` is written by the methods of `LocalKey` which replace its value
+    /// (`set`, `take`, `replace` and `with_borrow_mut`), and it is read by the others
+    /// (e.g., `with`).
+    fn record_static_pointer_args(
+        &mut self,
+        func: &mir::Operand<'tcx>,
+        args: &[Spanned<mir::Operand<'tcx>>],
+        location: mir::Location,
+    ) {
+        let tcx = self.analyzer.tcx;
+        let body = self.ctx.current_body.unwrap();
+        for arg in args {
+            let local = match &arg.node {
+                mir::Operand::Copy(place) | mir::Operand::Move(place) => {
+                    // A static passed by value is read, e.g., `foo(copy (*_1))`.
+                    self.record_static_deref(
+                        place,
+                        mir::visit::PlaceContext::NonMutatingUse(
+                            mir::visit::NonMutatingUseContext::Copy,
+                        ),
+                        location,
+                    );
+                    place.as_local()
+                }
+                mir::Operand::Constant(_) => None,
+            };
+            let Some(def_id) = local.and_then(|local| self.ctx.map_static_pointer.get(&local))
+            else {
+                continue;
+            };
+            let ty = arg.node.ty(body, tcx);
+            let writes = if Self::is_local_key(tcx, ty) {
+                func.const_fn_def().is_some_and(|(callee, _)| {
+                    matches!(
+                        tcx.item_name(callee).as_str(),
+                        "set" | "take" | "replace" | "with_borrow_mut"
+                    )
+                })
+            } else {
+                ty.is_mutable_ptr()
+            };
+            let call_kind = if writes {
+                CallKind::Writes
+            } else {
+                CallKind::Reads
+            };
+            self.add_static_access_edge(*def_id, call_kind, location);
+        }
+    }
+
+    /// Add an edge (`CallKind::Reads` or `CallKind::Writes`) between the current visited
+    /// function and the static `def_id`, unless the same edge is already present.
+    fn add_static_access_edge(
+        &mut self,
+        def_id: DefId,
+        call_kind: CallKind,
+        location: mir::Location,
+    ) {
+        if !self
+            .ctx
+            .set_static_accesses
+            .insert((def_id, call_kind.clone()))
+        {
+            return;
+        }
+        log::debug!(
+            "Adding a {:?} edge between the current visited function ({:?}) and the static ({:?})",
+            call_kind,
+            self.ctx.current_local_def_id.unwrap(),
+            def_id
+        );
+        let span = self.ctx.current_body.unwrap().source_info(location).span;
+        let unsafe_context = self.is_in_unsafe_context(span);
+        let fun_caller =
+            self.ctx.rl_graph_index_map[&(self.ctx.current_local_def_id.unwrap(), None)];
+        let fun_static = self.add_node_if_needed((def_id, None));
+        let mut edge = RLEdge::create((call_kind, Vec::new()));
+        edge.set_unsafe_context(unsafe_context);
+        self.add_edge_in_current_basic_block(fun_caller, fun_static, edge);
+    }
+
+    /// Add an edge whose call site is in the current visited basic block,
+    /// annotated with the control-flow context of the basic block.
+    fn add_edge_in_current_basic_block(
+        &mut self,
+        fun_caller: G::Index,
+        fun_callee: G::Index,
+        mut edge: RLEdge,
+    ) {
+        let bb = self.ctx.current_basic_block.unwrap();
+        let control_flow = self.ctx.current_control_flow.as_ref().unwrap();
+        edge.set_control_flow(
+            control_flow.loop_depth(bb),
+            control_flow.is_cleanup(bb),
+            control_flow.is_conditional(bb),
+        );
+        if let Some(span) = self.ctx.current_span {
+            edge.set_span(
+                self.analyzer
+                    .tcx
+                    .sess
+                    .source_map()
+                    .span_to_embeddable_string(span),
+            );
+        }
         self.rl_graph.rl_add_edge(fun_caller, fun_callee, edge);
     }
 
@@ -228,7 +1037,30 @@ where
         if let std::collections::hash_map::Entry::Vacant(entry) =
             self.ctx.rl_graph_index_map.entry(def_id)
         {
-            let node = RLNode::create(def_id.0, def_id.1);
+            let mut node = RLNode::create(def_id.0, def_id.1);
+            node.set_def_path(
+                self.analyzer.tcx.crate_name(def_id.0.krate).to_string(),
+                def_path(self.analyzer.tcx, def_id.0),
+            );
+            node.set_group_paths(
+                impl_path(self.analyzer.tcx, def_id.0),
+                def_path(self.analyzer.tcx, module_of(self.analyzer.tcx, def_id.0)),
+            );
+            if def_id.1.is_none() {
+                node.set_unsafe_fn(Self::is_unsafe_fn(self.analyzer.tcx, def_id.0));
+                node.set_public(def_id.0.as_local().is_some_and(|local_def_id| {
+                    self.analyzer
+                        .tcx
+                        .effective_visibilities(())
+                        .is_exported(local_def_id)
+                }));
+                node.set_local_fn(
+                    def_id
+                        .0
+                        .as_local()
+                        .is_some_and(|local_def_id| is_local_fn(self.analyzer.tcx, local_def_id)),
+                );
+            }
             let index = self.rl_graph.rl_add_node(node);
             entry.insert(index);
         }
@@ -359,6 +1191,7 @@ where
             TextMod::Green,
         );
         log::trace!("{}", message);
+        self.ctx.current_span = Some(statement.source_info.span);
         self.super_statement(statement, location)
     }
 
@@ -369,10 +1202,8 @@ where
             TextMod::Green,
         );
         log::trace!("{}", message);
-        let mir::Terminator {
-            source_info: _,
-            kind,
-        } = terminator;
+        let mir::Terminator { source_info, kind } = terminator;
+        self.ctx.current_span = Some(source_info.span);
 
         match kind {
             mir::TerminatorKind::Call {
@@ -412,6 +1243,26 @@ where
                     self.ctx.map_place_rlvalue.clone(),
                 );
 
+                // The signature is computed before resolving the call, since the resolver
+                // can drop the callee from the arguments.
+                let fn_sig = self.call_fn_sig(func, args, destination);
+
+                for arg in args.iter() {
+                    self.record_address_taken_operand(&arg.node);
+                }
+
+                if self.analyzer.cli_args.track_static_accesses {
+                    self.record_static_pointer_args(func, args, location);
+                }
+
+                if let Some(mutability) = self.runtime_borrow_of(func) {
+                    let index = self.ctx.rl_graph_index_map
+                        [&(self.ctx.current_local_def_id.unwrap(), None)];
+                    self.rl_graph
+                        .rl_node_mut(index)
+                        .add_runtime_borrow(mutability);
+                }
+
                 let (resolved_call, args) = RLCallResolver::new(&self.ctx, self.analyzer)
                     .resolve_call_def_id(func, args.clone(), self.ctx.current_basic_block.unwrap());
 
@@ -426,6 +1277,17 @@ where
                             destination.local,
                             RLValue::TermCallClone(args[0].node.clone()),
                         );
+                        // The argument of `Clone::clone` is a reference to the cloned value.
+                        let cloned_ty = args[0]
+                            .node
+                            .ty(self.ctx.current_body.unwrap(), self.analyzer.tcx)
+                            .peel_refs();
+                        let index = self.ctx.rl_graph_index_map
+                            [&(self.ctx.current_local_def_id.unwrap(), None)];
+                        self.rl_graph.rl_node_mut(index).add_clone(
+                            cloned_ty.to_string(),
+                            cloned_ty.ty_adt_def().map(|adt| adt.did()),
+                        );
                     }
                     CallKind::Function | CallKind::Closure | CallKind::Method => {
                         self.ctx.insert_map_place_rlvalue(
@@ -457,14 +1319,55 @@ where
                             RLValue::TermCallStaticallyUnknown(*def_id),
                         );
                     }
-                    CallKind::Unknown => unreachable!(),
+                    CallKind::Indirect
+                    | CallKind::Drop
+                    | CallKind::OverApproximated
+                    | CallKind::Reads
+                    | CallKind::Writes
+                    | CallKind::Unknown => {
+                        unreachable!()
+                    }
                 }
 
+                // The edges of the hidden calls are not added to the graph,
+                // but the state above must be updated anyway.
+                let call_source = CallSourceKind::new(*call_source, source_info.span);
+                let unsafe_context = self.is_in_unsafe_context(source_info.span);
+                let resolved_call = if self.is_call_source_hidden(call_source) {
+                    Vec::new()
+                } else {
+                    resolved_call
+                };
+
                 for ((def_id, promoted), call_kind) in resolved_call {
+                    if matches!(call_kind, CallKind::Function | CallKind::Method)
+                        && !def_id.is_local()
+                    {
+                        self.add_indirect_edges(def_id, &args, call_source, unsafe_context);
+                    }
                     if call_kind != CallKind::Unknown && call_kind != CallKind::Clone {
                         let args = self.update_args(&args, &call_kind);
                         let arg_weights = RLArgsResolver::new(&self.ctx).resolve(&call_kind, &args);
-                        self.add_edge((def_id, promoted), arg_weights);
+                        let arg_tys = RLArgsResolver::new(&self.ctx)
+                            .resolve_arg_tys(self.analyzer.tcx, &args);
+                        if call_kind == CallKind::StaticallyUnknown {
+                            self.add_statically_unknown_edge(
+                                location,
+                                fn_sig.clone(),
+                                arg_weights,
+                                arg_tys,
+                                call_source,
+                                unsafe_context,
+                            );
+                        } else {
+                            self.add_edge(
+                                (def_id, promoted),
+                                arg_weights,
+                                arg_tys,
+                                call_source,
+                                unsafe_context,
+                            );
+                        }
                     }
                 }
 
@@ -506,9 +1409,10 @@ where
                     ));
             }
             mir::TerminatorKind::Goto { target } => self.ctx.add_current_bb_as_parent_of(*target),
-            mir::TerminatorKind::Drop {
-                place: _, target, ..
-            } => self.ctx.add_current_bb_as_parent_of(*target),
+            mir::TerminatorKind::Drop { place, target, .. } => {
+                self.add_drop_edges(place);
+                self.ctx.add_current_bb_as_parent_of(*target)
+            }
             mir::TerminatorKind::Assert {
                 cond: _,
                 expected: _,
@@ -563,6 +1467,9 @@ where
 
         self.ctx
             .insert_map_place_rlvalue(place.local, RLValue::Rvalue(rvalue.clone()));
+        if self.analyzer.cli_args.track_static_accesses {
+            self.record_static_pointer(place, rvalue);
+        }
         self.super_assign(place, rvalue, location);
     }
 
@@ -579,6 +1486,9 @@ where
             context,
             location
         );
+        if self.analyzer.cli_args.track_static_accesses {
+            self.record_static_deref(place, context, location);
+        }
         self.super_place(place, context, location);
     }
 
@@ -620,6 +1530,8 @@ where
 
     // Call by the super_assign
     fn visit_rvalue(&mut self, rvalue: &mir::Rvalue<'tcx>, location: mir::Location) {
+        self.record_address_taken_rvalue(rvalue);
+
         let mut message = format!("Visiting the rvalue ({:?}, {:?})", rvalue, location);
         match rvalue {
             mir::Rvalue::Use(operand) => match operand {
@@ -706,5 +1618,61 @@ where
             }
         }
         log::trace!("{}", message);
+
+        // It visits the places used by the rvalue (e.g., `(*_1)` in `_0 = copy (*_1)`).
+        self.super_rvalue(rvalue, location);
+    }
+}
+
+/// Collect the spans of the `unsafe` blocks written by the user in a body,
+/// including the ones in the bodies of the nested closures.
+///
+/// The `unsafe` blocks generated by a macro are not collected (e.g., the ones in the
+/// expansion of `thread_local!`), since the user did not write them.
+struct UnsafeBlockCollector<'tcx> {
+    tcx: ty::TyCtxt<'tcx>,
+    // The number of closures enclosing the visited expression.
+    closure_depth: usize,
+    spans: Vec<(rustc_span::Span, usize)>,
+}
+
+impl<'tcx> UnsafeBlockCollector<'tcx> {
+    /// Return the spans of the `unsafe` blocks in the body of `local_def_id`,
+    /// each one with the number of closures enclosing it in the body.
+    fn collect(tcx: ty::TyCtxt<'tcx>, local_def_id: LocalDefId) -> Vec<(rustc_span::Span, usize)> {
+        let mut collector = Self {
+            tcx,
+            closure_depth: 0,
+            spans: Vec::new(),
+        };
+        collector.visit_body(tcx.hir_body_owned_by(local_def_id));
+        collector.spans
+    }
+}
+
+impl<'tcx> intravisit::Visitor<'tcx> for UnsafeBlockCollector<'tcx> {
+    type NestedFilter = nested_filter::OnlyBodies;
+
+    fn maybe_tcx(&mut self) -> Self::MaybeTyCtxt {
+        self.tcx
+    }
+
+    fn visit_block(&mut self, block: &'tcx hir::Block<'tcx>) {
+        if block.rules == hir::BlockCheckMode::UnsafeBlock(hir::UnsafeSource::UserProvided)
+            && !block.span.from_expansion()
+        {
+            self.spans.push((block.span, self.closure_depth));
+        }
+        intravisit::walk_block(self, block);
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        if let hir::ExprKind::Closure(_) = expr.kind {
+            self.closure_depth += 1;
+            intravisit::walk_expr(self, expr);
+            self.closure_depth -= 1;
+        } else {
+            intravisit::walk_expr(self, expr);
+        }
     }
 }
@@ -29,6 +29,8 @@ pub const MERGED_FILE_NAME: &str = "rlg_merged";
 
 pub const DUMMY_CRATE_NUM: CrateNum = CrateNum::from_u32(0); // Local crate
 pub const DUMMY_DEF_INDEX: DefIndex = DefIndex::from_u32(0); // Crarte root
+/// Placeholder returned by the call resolver when the callee is not known statically.
+/// It is never added to the graph: the visitor replaces it with a node for each call site.
 pub const STATICALLY_UNKNOWN_DEF_ID: DefId = DefId {
     krate: DUMMY_CRATE_NUM,
     index: DUMMY_DEF_INDEX,
@@ -2,6 +2,9 @@
 
 fn main() {
     env_logger::init();
+    if rusty_links::RustyLinks::run_command() {
+        return;
+    }
     rusty_links::instrument::cli_main(
         rusty_links::RustyLinks,
         rusty_links::RustyLinks::before_exec,
@@ -1,6 +1,8 @@
 #![feature(rustc_private)]
 
+extern crate rustc_abi;
 extern crate rustc_ast;
+extern crate rustc_borrowck;
 extern crate rustc_const_eval;
 extern crate rustc_data_structures;
 extern crate rustc_driver;
@@ -17,8 +19,12 @@ extern crate rustc_span;
 pub mod analysis;
 pub mod instrument;
 
-use analysis::{rl_analysis::RLAnalysis, Analyzer};
-use clap::Parser;
+use analysis::{
+    rl_analysis::{self, CallSourceKind, Granularity, RLAnalysis},
+    utils::{MERGED_FILE_NAME, RL_SERDE_FOLDER},
+    Analyzer,
+};
+use clap::{Parser, Subcommand};
 use instrument::{CrateFilter, RustcPlugin, RustcPluginArgs, Utf8Path};
 use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, env, path::PathBuf};
@@ -55,12 +61,252 @@ pub struct CliArgs {
     #[clap(long)]
     filter_with_file: Option<String>,
 
+    /// Link each statically unknown call site to every function and closure of the crate
+    /// whose address is taken and whose signature matches the one of the call site
+    #[clap(long)]
+    over_approximate_unknown_calls: bool,
+
+    /// Add an edge for each function reading or writing a `static`, a `static mut`
+    /// or a `thread_local!`
+    #[clap(long)]
+    track_static_accesses: bool,
+
+    /// The level at which the graph is printed with `--print-rl-graph` and
+    /// `--print-serialized-rl-graph`: the functions are collapsed into their `impl`,
+    /// module or crate, and the calls between them are aggregated. In a workspace, the
+    /// merged graph of its crates is printed too
+    #[clap(long, value_enum, default_value_t = CliGranularity::Fn)]
+    granularity: CliGranularity,
+
+    /// Hide the calls of the given class (e.g., `--hide-call-source operator`),
+    /// all the classes are shown by default
+    #[clap(long, value_enum)]
+    hide_call_source: Vec<CliCallSource>,
+
+    /// Print the safe public functions of the crate which transitively reach unsafe code
+    #[clap(long)]
+    print_unsafe_surface: bool,
+
+    /// Run the borrow checker on each function and record its ownership metrics
+    /// (borrows, reborrows, two-phase borrows, moves and borrows outliving the function)
+    #[clap(long)]
+    borrowck_facts: bool,
+
+    /// Print the ownership chain of each value created in the crate (and in the workspace),
+    /// i.e., the variables and arguments it is moved through until it is dropped or stored
+    #[clap(long)]
+    print_ownership_chains: bool,
+
+    /// Suggest to borrow the by-value parameters which are only read by their function,
+    /// with the call sites moving or cloning a value for them
+    #[clap(long)]
+    suggest_borrows: bool,
+
+    /// Print the methods of each type of the crate grouped by receiver (`self`, `&self`,
+    /// `&mut self`), with how they are called and the receivers which could be borrowed
+    #[clap(long)]
+    print_receivers: bool,
+
+    /// Print, for each type passed as argument in the crate, how many times it is moved,
+    /// copied, passed as a constant, cloned and borrowed, with the top call sites of each
+    #[clap(long)]
+    by_type: bool,
+
+    /// Suggest deriving `Copy` for the types of the crate whose fields are all `Copy`,
+    /// which are small and which are cloned at many call sites
+    #[clap(long)]
+    suggest_copy: bool,
+
+    /// Print the strongly connected components of the call graph, i.e., the recursive and
+    /// mutually recursive functions, with the calls forming the cycles
+    #[clap(long)]
+    print_sccs: bool,
+
+    /// Print the N functions with the highest transitive ownership cost, i.e., the ownership
+    /// score of their calls and clones plus the (damped) cost of their callees, and add the
+    /// cost to the nodes of the printed graph
+    #[clap(long, value_name = "N")]
+    transitive_cost: Option<usize>,
+
+    /// The factor multiplying the cost of the callees at each call for `--transitive-cost`,
+    /// e.g., 0.5 halves the cost of a callee at each level of the call chain
+    #[clap(long, default_value_t = 1.0)]
+    cost_damping: f64,
+
+    /// Print the N functions with the highest PageRank in the call graph weighted by the
+    /// ownership score of the calls, with their betweenness (on the shortest paths, where
+    /// a call is shorter the higher its ownership score) and weighted in/out degree
+    #[clap(long, value_name = "N")]
+    hotspots: Option<usize>,
+
+    /// Print the entry points (`main`, `#[test]`, exported and public functions) and the
+    /// functions which are not reachable from any of them. On a workspace, the functions
+    /// unreachable in the merged graph are printed too, where the public functions are not
+    /// entry points
+    #[clap(long)]
+    print_unreachable: bool,
+
+    /// Print the dominator tree of the functions reachable from each entry point (see
+    /// `--print-unreachable`), with the number of functions dominated by each function,
+    /// i.e., reached from the entry point only through it
+    #[clap(long)]
+    print_dominators: bool,
+
+    /// Check the calls against the layering rules of the given TOML file (e.g., the module
+    /// `domain` must not call the module `infra`) and print the cycles between the modules
+    #[clap(long, value_name = "FILE")]
+    layering_rules: Option<String>,
+
+    /// Match the calls against the rules of the given TOML file (e.g., moving a composite
+    /// value into the module `metrics` is an error) and print the findings with their
+    /// severity and message
+    #[clap(long, value_name = "FILE")]
+    rules: Option<String>,
+
+    /// The maximum size in bytes of a type for which `--suggest-copy` suggests `Copy`
+    #[clap(long, default_value_t = 16)]
+    copy_max_size: u64,
+
+    /// The minimum number of clones of a type for which `--suggest-copy` suggests `Copy`
+    #[clap(long, default_value_t = 2)]
+    copy_min_clones: usize,
+
+    #[clap(subcommand)]
+    command: Option<RLCommand>,
+
     #[clap(last = true)]
     // mytool --allcaps -- some extra args here
     //                     ^^^^^^^^^^^^^^^^^^^^ these are cargo args
     cargo_args: Vec<String>,
 }
 
+/// The levels at which the graph can be printed with `--granularity`
+/// (see `Granularity`).
+#[derive(clap::ValueEnum, Serialize, Deserialize, Debug, Copy, Clone, PartialEq, Default)]
+pub enum CliGranularity {
+    /// A node for each function (or closure), i.e., the graph is not condensed
+    #[default]
+    Fn,
+    /// A node for each type with an `impl`, collecting its methods
+    Impl,
+    /// A node for each module
+    Module,
+    /// A node for each crate
+    Crate,
+}
+
+impl From<CliGranularity> for Granularity {
+    fn from(granularity: CliGranularity) -> Self {
+        match granularity {
+            CliGranularity::Fn => Granularity::Fn,
+            CliGranularity::Impl => Granularity::Impl,
+            CliGranularity::Module => Granularity::Module,
+            CliGranularity::Crate => Granularity::Crate,
+        }
+    }
+}
+
+/// The classes of calls which can be hidden with `--hide-call-source`
+/// (see `CallSourceKind`).
+#[derive(clap::ValueEnum, Serialize, Deserialize, Debug, Copy, Clone, PartialEq)]
+pub enum CliCallSource {
+    /// The calls written by the user
+    User,
+    /// The overloaded operators, including the implicit dereferences of the auto-deref
+    Operator,
+    /// The desugaring of the `?` operator
+    QuestionMark,
+    /// The desugaring of a `for` loop
+    ForLoop,
+    /// Any other call generated by the compiler
+    Desugaring,
+}
+
+impl From<CliCallSource> for CallSourceKind {
+    fn from(call_source: CliCallSource) -> Self {
+        match call_source {
+            CliCallSource::User => CallSourceKind::User,
+            CliCallSource::Operator => CallSourceKind::Operator,
+            CliCallSource::QuestionMark => CallSourceKind::QuestionMark,
+            CliCallSource::ForLoop => CallSourceKind::ForLoop,
+            CliCallSource::Desugaring => CallSourceKind::Desugaring,
+        }
+    }
+}
+
+/// The queries on the graphs of the last analysis, which do not analyze the crates again.
+#[derive(Subcommand, Serialize, Deserialize, Debug, Clone)]
+pub enum RLCommand {
+    /// Print the cheapest (or most expensive) call paths between two functions, weighted by
+    /// the ownership score of the calls, with the arguments of each call
+    Path {
+        /// The path of the first function with its crate, e.g., `my_crate::parser::parse`
+        #[clap(long)]
+        from: String,
+
+        /// The path of the last function with its crate
+        #[clap(long)]
+        to: String,
+
+        /// The number of paths to print
+        #[clap(long, default_value_t = 1)]
+        k: usize,
+
+        /// Print the most expensive paths instead of the cheapest ones, searched among the
+        /// paths with a bounded number of calls
+        #[clap(long)]
+        most_expensive: bool,
+
+        /// The graph to query, by default the merged graph of the workspace
+        #[clap(long, value_name = "FILE")]
+        graph: Option<String>,
+    },
+    /// Print the functions changed since a git revision and the functions (and tests) which
+    /// transitively call them, grouped by crate
+    Impact {
+        /// The git revision to compare the working tree with, e.g., `HEAD~1` or `main`.
+        /// The untracked files which are not ignored are entirely changed
+        #[clap(long)]
+        since: String,
+
+        /// The graph to query, by default the merged graph of the workspace
+        #[clap(long, value_name = "FILE")]
+        graph: Option<String>,
+    },
+}
+
+impl CliArgs {
+    /// Parse the arguments of the CLI, exiting with the error of clap if they are invalid.
+    fn from_env() -> Self {
+        Self::try_from_env().unwrap_or_else(|error| error.exit())
+    }
+
+    /// Parse the arguments of the CLI.
+    fn try_from_env() -> Result<Self, clap::Error> {
+        // We cannot use `#[cfg(test)]` here because the test suite installs the plugin.
+        // In other words, in the test suite we need to compile (install) the plugin with
+        // `--features test-mode` to skip the first argument that is the `cargo` command.
+        //
+        // # Explanation:
+        //
+        // ## Test
+        //
+        // In tests we run something like `cargo rusty-links --print-dot` because the plugin is installed as a binary in a temporary directory.
+        // It is expanded to `/tmp/rusty-links/bin/cargo-rusty-links rusty-links --print-dot`, so we need to skip the first argument because it is the `cargo` command.
+        //
+        // ## Cli
+        // In the CLI we run something like `cargo run --bin rusty-links -- --print-dot` or `./target/debug/cargo-rusty-links --print-dot`.
+        // It is expanded to `.target/debug/cargo-rusty-links --print-dot`, so we don't need to skip the first argument.
+        #[cfg(feature = "test-mode")]
+        let args = CliArgs::try_parse_from(env::args().skip(1));
+
+        #[cfg(not(feature = "test-mode"))]
+        let args = CliArgs::try_parse_from(env::args());
+
+        args
+    }
+}
+
 // This struct is the plugin provided to the intrumentation module,
 // and it must be exported for use by the CLI/driver binaries.
 pub struct RustyLinks;
@@ -70,9 +316,61 @@ impl RustyLinks {
         log::debug!("Before exec");
         RLAnalysis::<rustworkx_core::petgraph::graph::DiGraph<_, _, _>>::clear_rl_folder();
     }
+    /// Run the subcommand of the CLI, if any, and return whether it was run.
+    /// The arguments which are not parsed by clap (e.g., `-V`) are left to `cli_main`.
+    pub fn run_command() -> bool {
+        let Some(command) = CliArgs::try_from_env().ok().and_then(|args| args.command) else {
+            return false;
+        };
+        let (RLCommand::Path { graph, .. } | RLCommand::Impact { graph, .. }) = &command;
+        let graph = graph
+            .clone()
+            .unwrap_or_else(|| format!("{}/{}.rlg", RL_SERDE_FOLDER, MERGED_FILE_NAME));
+        let rl_graph = RLAnalysis::<rustworkx_core::petgraph::graph::DiGraph<_, _, _>>::deserialized_rl_graph_from_file(&graph);
+        let result = match command {
+            RLCommand::Path {
+                from,
+                to,
+                k,
+                most_expensive,
+                ..
+            } => analysis::print_paths(&rl_graph, &from, &to, k, most_expensive),
+            RLCommand::Impact { since, .. } => analysis::print_impact(&rl_graph, &since),
+        };
+        if let Err(error) = result {
+            eprintln!("error: {}", error);
+            std::process::exit(1);
+        }
+        true
+    }
+
     pub fn after_exec() {
         log::debug!("After exec");
-        RLAnalysis::<rustworkx_core::petgraph::graph::DiGraph<_, _, _>>::merge_all_rl_graphs();
+        let merged_rl_graph =
+            RLAnalysis::<rustworkx_core::petgraph::graph::DiGraph<_, _, _>>::merge_all_rl_graphs();
+        let cli_args = CliArgs::from_env();
+        let granularity = Granularity::from(cli_args.granularity);
+        if granularity != Granularity::Fn
+            && (cli_args.print_rl_graph || cli_args.print_serialized_rl_graph)
+        {
+            // The graph of each crate is printed by its driver, the one of the workspace
+            // is printed only condensed, e.g., to show the calls between the crates.
+            analysis::print_condensed_rl_graph(
+                &merged_rl_graph,
+                granularity,
+                cli_args.print_rl_graph,
+                cli_args.print_serialized_rl_graph,
+            );
+        }
+        if cli_args.print_ownership_chains {
+            // The values passed to the functions of the other crates are followed too.
+            analysis::print_ownership_chains(&merged_rl_graph, "the workspace");
+        }
+        if cli_args.print_unreachable {
+            // The public functions are not entry points of the workspace, so the ones
+            // which are never called by the other crates are reported.
+            analysis::print_reachability(&merged_rl_graph, "the workspace", false);
+        }
     }
 }
 
@@ -95,25 +393,7 @@ impl RustcPlugin for RustyLinks {
     // If one of the CLI arguments was a specific file to analyze, then you
     // could provide a different filter.
     fn args(&self, _target_dir: &Utf8Path) -> RustcPluginArgs<Self::Args> {
-        // We cannot use `#[cfg(test)]` here because the test suite installs the plugin.
-        // In other words, in the test suite we need to compile (install) the plugin with
-        // `--features test-mode` to skip the first argument that is the `cargo` command.
-        //
-        // # Explanation:
-        //
-        // ## Test
-        //
-        // In tests we run something like `cargo rusty-links --print-dot` because the plugin is installed as a binary in a temporary directory.
-        // It is expanded to `/tmp/rusty-links/bin/cargo-rusty-links rusty-links --print-dot`, so we need to skip the first argument because it is the `cargo` command.
-        //
-        // ## Cli
-        // In the CLI we run something like `cargo run --bin rusty-links -- --print-dot` or `./target/debug/cargo-rusty-links --print-dot`.
-        // It is expanded to `.target/debug/cargo-rusty-links --print-dot`, so we don't need to skip the first argument.
-        #[cfg(feature = "test-mode")]
-        let args = CliArgs::parse_from(env::args().skip(1));
-
-        #[cfg(not(feature = "test-mode"))]
-        let args = CliArgs::parse_from(env::args());
+        let args = CliArgs::from_env();
 
         // let filter = CrateFilter::AllCrates;
         // let filter = CrateFilter::CrateContainingFile(PathBuf::from("compiler/rustc/src/main.rs"));
@@ -158,6 +438,10 @@ impl rustc_driver::Callbacks for PluginCallbacks {
         if self.args.use_unoptimized_mir {
             config.opts.unstable_opts.mir_opt_level = Some(0);
         }
+
+        if self.args.borrowck_facts {
+            config.override_queries = Some(rl_analysis::override_queries);
+        }
     }
 
     /// Called after expansion. Return value instructs the compiler whether to
@@ -0,0 +1,63 @@
+#[derive(Clone)]
+pub struct Config {
+    verbose: bool,
+    name: String,
+}
+
+impl Config {
+    pub fn describe(self) -> usize {
+        self.name.len()
+    }
+
+    pub fn into_name(self) -> String {
+        self.name
+    }
+}
+
+fn greet(name: String) -> usize {
+    name.len()
+}
+
+fn total(values: Vec<u32>) -> u32 {
+    values.iter().sum()
+}
+
+fn is_verbose(config: Config) -> bool {
+    config.verbose
+}
+
+fn keep(mut names: Vec<String>, name: String) -> Vec<String> {
+    names.push(name);
+    names
+}
+
+fn rename(mut config: Config) -> bool {
+    config.name = String::new();
+    config.verbose
+}
+
+fn by_ref(config: &Config) -> bool {
+    config.verbose
+}
+
+fn copy(value: u32) -> u32 {
+    value
+}
+
+pub fn main() {
+    let name = String::from("rusty");
+    greet(name.clone());
+    greet(name);
+    total(vec![1, 2]);
+    let config = Config {
+        verbose: true,
+        name: String::new(),
+    };
+    is_verbose(config.clone());
+    rename(config.clone());
+    by_ref(&config);
+    config.clone().describe();
+    config.into_name();
+    keep(Vec::new(), String::new());
+    copy(1);
+}
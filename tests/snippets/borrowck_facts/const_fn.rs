@@ -0,0 +1,15 @@
+fn main() {
+    let buffer = buffer();
+    let _ = len(&buffer);
+}
+
+fn buffer() -> [u8; len(&[1, 2, 3])] {
+    [0; len(&[1, 2, 3])]
+}
+
+const fn len(items: &[u8]) -> usize {
+    let mut len = 0;
+    let len_mut = &mut len;
+    *len_mut = items.len();
+    len
+}
@@ -0,0 +1,43 @@
+pub fn shared(v: &Vec<u32>) -> usize {
+    let r = &v[0];
+    let s = &v;
+    *r as usize + s.len()
+}
+
+pub fn push_len(v: &mut Vec<usize>) {
+    v.push(v.len());
+}
+
+pub fn first<'a>(v: &'a [u32]) -> &'a u32 {
+    &v[0]
+}
+
+pub fn consume(v: Vec<u32>) -> Vec<u32> {
+    let w = v;
+    let x = (w, 1);
+    x.0
+}
+
+pub fn local() -> u32 {
+    let mut a = 1;
+    let b = &mut a;
+    *b += 1;
+    a
+}
+
+pub const fn constant() -> usize {
+    3
+}
+
+pub fn array() -> [u8; constant()] {
+    let f = |x: &u8| *x;
+    [f(&0); constant()]
+}
+
+pub fn first_mut<'a>(v: &'a mut [u32]) -> &'a mut u32 {
+    &mut v[0]
+}
+
+pub fn leak() -> &'static mut u32 {
+    Box::leak(Box::new(1))
+}
@@ -0,0 +1,35 @@
+#[derive(Clone)]
+pub struct Account {
+    balance: u64,
+}
+
+fn consume(account: Account) -> u64 {
+    account.balance
+}
+
+fn inspect(account: &Account) -> u64 {
+    account.balance
+}
+
+fn deposit(account: &mut Account, amount: u64) {
+    account.balance += amount;
+}
+
+fn log(message: &str) -> usize {
+    message.len()
+}
+
+pub fn main() {
+    let mut account = Account { balance: 0 };
+    deposit(&mut account, 10);
+    deposit(&mut account, 20);
+    inspect(&account);
+    inspect(&account);
+    inspect(&account);
+    consume(account.clone());
+    consume(account.clone());
+    log("done");
+    let amount = 5;
+    deposit(&mut account, amount);
+    consume(account);
+}
@@ -0,0 +1,45 @@
+use std::ops::{Add, Deref, Index};
+
+struct Meters(u32);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+struct Wrapper(Vec<u32>);
+
+impl Deref for Wrapper {
+    type Target = Vec<u32>;
+
+    fn deref(&self) -> &Vec<u32> {
+        &self.0
+    }
+}
+
+impl Index<usize> for Wrapper {
+    type Output = u32;
+
+    fn index(&self, index: usize) -> &u32 {
+        &self.0[index]
+    }
+}
+
+fn parse(s: &str) -> Result<u32, std::num::ParseIntError> {
+    let n = s.parse::<u32>()?;
+    Ok(n)
+}
+
+fn main() {
+    let _m = Meters(1) + Meters(2);
+    let w = Wrapper(vec![1, 2, 3]);
+    let _first = w[0];
+    let _len = w.len();
+    for x in vec![1, 2, 3] {
+        let _ = x;
+    }
+    let _ = parse("1");
+}
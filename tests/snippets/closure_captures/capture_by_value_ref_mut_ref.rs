@@ -0,0 +1,23 @@
+struct T {
+    _value: i32,
+}
+
+fn main() {
+    let by_value = T { _value: 10 };
+    let by_ref = T { _value: 10 };
+    let mut by_mut_ref = Vec::new();
+
+    let consume = move || {
+        let t = by_value;
+        let _ = t;
+    };
+    consume();
+
+    let read = || {
+        let _ = &by_ref;
+    };
+    read();
+
+    let mut push = || by_mut_ref.push(10);
+    push();
+}
@@ -0,0 +1,4 @@
+fn main() {
+    let v = vec![1, 2, 3];
+    let _: Vec<i32> = v.iter().map(|x| x + 1).collect();
+}
@@ -0,0 +1,8 @@
+fn double(x: i32) -> i32 {
+    x * 2
+}
+
+fn main() {
+    let o = Some(10);
+    let _ = o.map_or_else(|| 0, double);
+}
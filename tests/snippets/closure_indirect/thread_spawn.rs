@@ -0,0 +1,11 @@
+struct T {
+    _value: i32,
+}
+
+fn main() {
+    let t = T { _value: 10 };
+    let handle = std::thread::spawn(move || {
+        let _ = t;
+    });
+    handle.join().unwrap();
+}
@@ -0,0 +1,27 @@
+fn once() {}
+
+fn inner() {}
+
+fn innermost() {}
+
+fn maybe() {}
+
+fn after() {}
+
+fn main() {
+    let values: Vec<u32> = Vec::new();
+    once();
+    for i in 0..10 {
+        inner();
+        let mut j = 0;
+        while j < i {
+            innermost();
+            j += 1;
+        }
+        if i % 2 == 0 {
+            maybe();
+        }
+    }
+    after();
+    let _ = values.len();
+}
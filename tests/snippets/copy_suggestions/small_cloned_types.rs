@@ -0,0 +1,62 @@
+#[derive(Clone)]
+pub struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Clone)]
+pub enum Direction {
+    North,
+    South,
+}
+
+#[derive(Clone)]
+pub struct Large {
+    values: [u64; 8],
+}
+
+#[derive(Clone)]
+pub struct Named {
+    name: String,
+}
+
+#[derive(Clone)]
+pub struct Borrowed<'a> {
+    value: &'a u32,
+}
+
+#[derive(Clone, Copy)]
+pub struct AlreadyCopy {
+    x: i32,
+}
+
+fn distance(a: Point, b: Point) -> i32 {
+    (a.x - b.x).abs() + (a.y - b.y).abs()
+}
+
+fn turn(direction: Direction) -> Direction {
+    direction
+}
+
+pub fn main() {
+    let origin = Point { x: 0, y: 0 };
+    let target = Point { x: 1, y: 2 };
+    distance(origin.clone(), target.clone());
+    distance(origin.clone(), target);
+    let north = Direction::North;
+    turn(north.clone());
+    let large = Large { values: [0; 8] };
+    let _ = (large.clone(), large.clone());
+    let named = Named { name: String::new() };
+    let _ = (named.clone(), named.clone());
+    let value = 1;
+    let borrowed = Borrowed { value: &value };
+    let _ = (borrowed.clone(), borrowed.clone());
+    let copy = AlreadyCopy { x: 1 };
+    let _ = (copy.clone(), copy.clone());
+}
+
+pub fn other() {
+    let origin = Point { x: 0, y: 0 };
+    let _ = origin.clone();
+}
@@ -0,0 +1,23 @@
+pub fn api(n: u32) -> u32 {
+    parse(n) + render(n)
+}
+
+fn parse(n: u32) -> u32 {
+    tokenize(n) + validate(n)
+}
+
+fn tokenize(n: u32) -> u32 {
+    shared(n)
+}
+
+fn validate(n: u32) -> u32 {
+    n
+}
+
+pub fn render(n: u32) -> u32 {
+    shared(n)
+}
+
+fn shared(n: u32) -> u32 {
+    n
+}
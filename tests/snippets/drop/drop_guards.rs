@@ -0,0 +1,53 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::sync::Mutex;
+
+struct Wrapper {
+    _value: i32,
+}
+
+impl Drop for Wrapper {
+    fn drop(&mut self) {}
+}
+
+struct View<'a, T> {
+    _value: &'a T,
+    _marker: PhantomData<fn() -> T>,
+}
+
+fn read(cell: &RefCell<Wrapper>) -> i32 {
+    let wrapper = cell.borrow();
+    wrapper._value
+}
+
+fn write(cell: &RefCell<Wrapper>) {
+    let mut wrapper = cell.borrow_mut();
+    wrapper._value += 1;
+}
+
+fn lock(mutex: &Mutex<Wrapper>) -> i32 {
+    let wrapper = mutex.lock().unwrap();
+    wrapper._value
+}
+
+fn view(wrapper: &Wrapper) -> i32 {
+    let view = View {
+        _value: wrapper,
+        _marker: PhantomData,
+    };
+    view._value._value
+}
+
+fn owned(wrapper: Option<Wrapper>) -> bool {
+    wrapper.is_some()
+}
+
+fn main() {
+    let cell = RefCell::new(Wrapper { _value: 1 });
+    let mutex = Mutex::new(Wrapper { _value: 2 });
+    read(&cell);
+    write(&cell);
+    lock(&mutex);
+    view(&Wrapper { _value: 3 });
+    owned(Some(Wrapper { _value: 4 }));
+}
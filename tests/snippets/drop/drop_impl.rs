@@ -0,0 +1,24 @@
+struct T {
+    _value: i32,
+}
+
+impl Drop for T {
+    fn drop(&mut self) {}
+}
+
+struct Wrapper {
+    _t: T,
+}
+
+fn consume(t: T) {
+    let _ = t;
+}
+
+fn main() {
+    let t = T { _value: 10 };
+    let _wrapper = Wrapper { _t: T { _value: 10 } };
+    let _ts = vec![T { _value: 10 }];
+    if t._value == 10 {
+        consume(t);
+    }
+}
@@ -0,0 +1,43 @@
+pub mod shapes {
+    pub struct Circle {
+        pub radius: f64,
+    }
+
+    impl Circle {
+        pub fn new(radius: f64) -> Self {
+            Circle { radius }
+        }
+
+        pub fn area(&self) -> f64 {
+            square(self.radius) * 3.14
+        }
+    }
+
+    impl std::fmt::Display for Circle {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "circle of area {}", self.area())
+        }
+    }
+
+    fn square(x: f64) -> f64 {
+        x * x
+    }
+}
+
+pub mod report {
+    use crate::shapes::Circle;
+
+    pub fn describe(radii: Vec<f64>) -> Vec<String> {
+        radii
+            .into_iter()
+            .map(|radius| Circle::new(radius).to_string())
+            .collect()
+    }
+}
+
+pub fn main() {
+    let descriptions = report::describe(vec![1.0, 2.0]);
+    let circle = shapes::Circle::new(3.0);
+    let _ = circle.area();
+    let _ = descriptions.len();
+}
@@ -0,0 +1,42 @@
+struct Order {
+    id: u32,
+    items: Vec<u32>,
+}
+
+fn validate(order: &Order) -> bool {
+    !order.items.is_empty()
+}
+
+fn store(order: Order) -> u32 {
+    order.id
+}
+
+fn process(order: Order) -> u32 {
+    if validate(&order) {
+        store(order)
+    } else {
+        0
+    }
+}
+
+fn from_web(id: u32) -> u32 {
+    process(Order { id, items: vec![1] })
+}
+
+fn from_cli(id: u32) -> u32 {
+    process(Order { id, items: vec![2] })
+}
+
+fn from_batch(ids: &[u32]) -> u32 {
+    let mut total = 0;
+    for id in ids {
+        total += process(Order { id: *id, items: vec![3] });
+    }
+    total
+}
+
+pub fn main() {
+    from_web(1);
+    from_cli(2);
+    from_batch(&[3, 4]);
+}
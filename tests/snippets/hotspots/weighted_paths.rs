@@ -0,0 +1,21 @@
+pub struct Data {
+    values: Vec<u8>,
+}
+
+fn target(data: Data, extra: u8) -> usize {
+    data.values.len() + extra as usize
+}
+
+// The path through `heavy` moves the data at each call.
+fn heavy(data: Data) -> usize {
+    target(data, 0)
+}
+
+// The path through `light` only copies a number until the last call.
+fn light(extra: u8) -> usize {
+    target(Data { values: Vec::new() }, extra)
+}
+
+pub fn api(data: Data, extra: u8) -> usize {
+    heavy(data) + light(extra)
+}
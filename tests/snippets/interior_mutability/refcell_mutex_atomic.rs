@@ -0,0 +1,34 @@
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+fn push(cell: &RefCell<Vec<u32>>, value: u32) {
+    cell.borrow_mut().push(value);
+}
+
+fn len(cell: &RefCell<Vec<u32>>) -> usize {
+    cell.borrow().len()
+}
+
+fn lock_and_add(mutex: &Mutex<u32>, value: u32) {
+    *mutex.lock().unwrap() += value;
+}
+
+fn bump(counter: &AtomicUsize) {
+    counter.fetch_add(1, Ordering::Relaxed);
+}
+
+fn sum(values: &Vec<u32>) -> u32 {
+    values.iter().sum()
+}
+
+fn main() {
+    let cell = RefCell::new(Vec::new());
+    push(&cell, 1);
+    let _ = len(&cell);
+    let mutex = Mutex::new(0);
+    lock_and_add(&mutex, 1);
+    let counter = AtomicUsize::new(0);
+    bump(&counter);
+    let _ = sum(&vec![1, 2]);
+}
@@ -0,0 +1,50 @@
+pub mod domain {
+    pub struct Order {
+        pub id: u32,
+    }
+
+    pub fn place_order(id: u32) -> u32 {
+        let order = Order { id };
+        crate::infra::save(&order)
+    }
+
+    pub fn validate(order: &Order) -> bool {
+        order.id > 0
+    }
+}
+
+pub mod infra {
+    use crate::domain::Order;
+
+    pub fn save(order: &Order) -> u32 {
+        if crate::domain::validate(order) {
+            order.id
+        } else {
+            0
+        }
+    }
+}
+
+pub mod legacy {
+    pub fn old_format(id: u32) -> String {
+        format!("#{}", pad(id))
+    }
+
+    fn pad(id: u32) -> u32 {
+        id
+    }
+}
+
+pub mod adapters {
+    pub fn format(id: u32) -> String {
+        crate::legacy::old_format(id)
+    }
+}
+
+pub mod api {
+    pub fn handle(id: u32) -> String {
+        crate::domain::place_order(id);
+        crate::adapters::format(id);
+        crate::legacy::old_format(id)
+    }
+}
@@ -0,0 +1,60 @@
+pub struct Buffer {
+    data: Vec<u8>,
+}
+
+impl Buffer {
+    pub fn new() -> Buffer {
+        Buffer { data: Vec::new() }
+    }
+}
+
+pub struct Holder {
+    buffer: Option<Buffer>,
+}
+
+fn fill(mut buf: Buffer) -> Buffer {
+    buf.data.push(1);
+    buf
+}
+
+fn sink(buf: Buffer) {
+    let owned = buf;
+    let _ = owned.data.len();
+}
+
+fn keep(holder: &mut Holder, buf: Buffer) {
+    holder.buffer = Some(buf);
+}
+
+pub fn pipeline() {
+    let buf = Buffer::new();
+    let filled = fill(buf);
+    sink(filled);
+}
+
+pub fn store(holder: &mut Holder) {
+    let buf = Buffer { data: vec![1, 2] };
+    keep(holder, buf);
+}
+
+pub fn local() {
+    let buf = Buffer::new();
+    let _ = buf.data.len();
+}
+
+pub fn external() -> Vec<Buffer> {
+    let mut buffers = Vec::new();
+    buffers.push(Buffer::new());
+    buffers
+}
+
+pub fn create() -> Buffer {
+    let buf = Buffer::new();
+    fill(buf)
+}
+
+pub fn apply() {
+    let buf = Buffer::new();
+    let consume = |buf: Buffer| sink(buf);
+    consume(buf);
+}
@@ -0,0 +1,58 @@
+pub fn api(n: u32) -> u32 {
+    helper(n) + apply(through_pointer, n)
+}
+
+fn helper(n: u32) -> u32 {
+    [n].iter().map(|x| through_closure(*x)).sum()
+}
+
+fn through_closure(n: u32) -> u32 {
+    n + 1
+}
+
+fn through_pointer(n: u32) -> u32 {
+    n * 2
+}
+
+fn apply(f: fn(u32) -> u32, n: u32) -> u32 {
+    f(n)
+}
+
+#[no_mangle]
+pub extern "C" fn exported(n: u32) -> u32 {
+    from_export(n)
+}
+
+fn from_export(n: u32) -> u32 {
+    n
+}
+
+fn dead(n: u32) -> u32 {
+    dead_callee(n)
+}
+
+fn dead_callee(n: u32) -> u32 {
+    n
+}
+
+struct Unused;
+
+impl Unused {
+    fn dead_method(&self) -> u32 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tested(n: u32) -> u32 {
+        n
+    }
+
+    #[test]
+    fn it_works() {
+        assert_eq!(tested(1), 1);
+    }
+}
@@ -0,0 +1,51 @@
+#[derive(Clone)]
+pub struct Counter {
+    value: u32,
+}
+
+pub trait Describe {
+    fn describe(self) -> u32;
+}
+
+impl Counter {
+    pub fn new() -> Self {
+        Counter { value: 0 }
+    }
+
+    pub fn total(self) -> u32 {
+        self.value
+    }
+
+    pub fn into_value(self) -> u32 {
+        self.value
+    }
+
+    pub fn get(&self) -> u32 {
+        self.value
+    }
+
+    pub fn increment(&mut self) {
+        self.value += 1;
+    }
+
+    pub fn boxed(self: Box<Self>) -> u32 {
+        self.value
+    }
+}
+
+impl Describe for Counter {
+    fn describe(self) -> u32 {
+        self.value
+    }
+}
+
+pub fn main() {
+    let mut counter = Counter::new();
+    counter.increment();
+    counter.get();
+    counter.clone().total();
+    counter.clone().total();
+    counter.clone().describe();
+    Box::new(counter.clone()).boxed();
+    counter.into_value();
+}
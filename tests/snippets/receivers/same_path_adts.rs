@@ -0,0 +1,32 @@
+pub trait Describe {
+    fn describe(self) -> String;
+}
+
+fn main() {
+    {
+        #[derive(Clone)]
+        struct Local(u32);
+
+        impl Describe for Local {
+            fn describe(self) -> String {
+                format!("{}", self.0)
+            }
+        }
+
+        let local = Local(1);
+        let _ = local.clone().describe();
+    }
+    {
+        #[derive(Clone)]
+        struct Local(&'static str);
+
+        impl Describe for Local {
+            fn describe(self) -> String {
+                self.0.to_string()
+            }
+        }
+
+        let local = Local("one");
+        let _ = local.describe();
+    }
+}
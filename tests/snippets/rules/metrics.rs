@@ -0,0 +1,28 @@
+pub mod metrics {
+    pub struct Sample {
+        pub name: String,
+        pub value: u64,
+    }
+
+    pub fn record(sample: Sample) -> u64 {
+        sample.value
+    }
+
+    pub fn record_value(value: u64) -> u64 {
+        value
+    }
+
+    pub fn inspect(sample: &Sample) -> usize {
+        sample.name.len()
+    }
+}
+
+pub mod handlers {
+    use crate::metrics::{self, Sample};
+
+    pub fn handle(name: String) -> u64 {
+        let sample = Sample { name, value: 1 };
+        let len = metrics::inspect(&sample);
+        metrics::record_value(len as u64) + metrics::record(sample)
+    }
+}
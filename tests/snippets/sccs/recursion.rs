@@ -0,0 +1,52 @@
+fn factorial(n: u64) -> u64 {
+    if n == 0 {
+        1
+    } else {
+        n * factorial(n - 1)
+    }
+}
+
+fn is_even(n: u64) -> bool {
+    if n == 0 {
+        true
+    } else {
+        is_odd(n - 1)
+    }
+}
+
+fn is_odd(n: u64) -> bool {
+    if n == 0 {
+        false
+    } else {
+        is_even(n - 1)
+    }
+}
+
+fn walk(depth: u32) -> u32 {
+    let step = |d: u32| if d == 0 { 0 } else { walk(d - 1) };
+    step(depth)
+}
+
+fn apply(f: fn(u32) -> u32, n: u32) -> u32 {
+    f(n)
+}
+
+fn countdown(n: u32) -> u32 {
+    if n == 0 {
+        0
+    } else {
+        apply(countdown, n - 1)
+    }
+}
+
+fn leaf() -> u32 {
+    1
+}
+
+pub fn main() {
+    factorial(5);
+    is_even(4);
+    walk(3);
+    countdown(2);
+    leaf();
+}
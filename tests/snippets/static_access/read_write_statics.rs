@@ -0,0 +1,40 @@
+use std::cell::Cell;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static LIMIT: u32 = 10;
+static mut COUNTER: u32 = 0;
+static HITS: AtomicU32 = AtomicU32::new(0);
+
+thread_local! {
+    static DEPTH: Cell<u32> = const { Cell::new(0) };
+}
+
+fn read_limit() -> u32 {
+    LIMIT
+}
+
+fn increment() {
+    unsafe {
+        COUNTER += 1;
+    }
+}
+
+fn hit() {
+    HITS.fetch_add(1, Ordering::Relaxed);
+}
+
+fn enter() {
+    DEPTH.with(|depth| depth.set(depth.get() + 1));
+}
+
+fn reset() {
+    DEPTH.set(0);
+}
+
+fn main() {
+    let _ = read_limit();
+    increment();
+    hit();
+    enter();
+    reset();
+}
@@ -0,0 +1,24 @@
+struct T {
+    _value: i32,
+}
+
+fn apply(f: fn(T), t: T) {
+    f(t);
+}
+
+fn apply_twice(f: fn(T), g: fn(T)) {
+    f(T { _value: 10 });
+    g(T { _value: 10 });
+}
+
+fn test(t: T) {
+    let _ = t;
+}
+
+fn test_other(_: i32) {}
+
+fn main() {
+    apply(test, T { _value: 10 });
+    apply_twice(test, test);
+    let _ = test_other as fn(i32);
+}
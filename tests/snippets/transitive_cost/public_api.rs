@@ -0,0 +1,32 @@
+#[derive(Clone)]
+pub struct Config {
+    pub name: String,
+}
+
+fn leaf(value: u32) -> u32 {
+    value
+}
+
+fn store(config: Config) -> usize {
+    config.name.len()
+}
+
+fn prepare(config: &Config) -> usize {
+    store(config.clone()) + leaf(1) as usize
+}
+
+fn ping(n: u32) -> u32 {
+    if n == 0 {
+        leaf(n)
+    } else {
+        pong(n - 1)
+    }
+}
+
+fn pong(n: u32) -> u32 {
+    ping(n)
+}
+
+pub fn api(config: Config) -> usize {
+    prepare(&config) + ping(3) as usize
+}
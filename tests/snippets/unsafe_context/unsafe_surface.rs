@@ -0,0 +1,30 @@
+static mut COUNTER: u32 = 0;
+
+unsafe fn raw_increment() {
+    COUNTER += 1;
+}
+
+fn read_counter() -> u32 {
+    unsafe { COUNTER }
+}
+
+pub fn safe_increment() {
+    unsafe { raw_increment() }
+}
+
+pub fn indirect_increment() {
+    safe_increment();
+}
+
+pub fn total() -> u32 {
+    helper()
+}
+
+fn helper() -> u32 {
+    read_counter() + 1
+}
+
+pub fn pure() -> u32 {
+    let f = |x: u32| x + 1;
+    f(1)
+}
@@ -0,0 +1,26 @@
+mod utils;
+
+mod test_snippets_borrow_suggestions {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/borrow_suggestions";
+
+    #[test]
+    fn test_only_read_params() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/only_read_params.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--suggest-borrows"])?;
+
+        assert!(output.contains("`greet` takes `name: std::string::String` by value but only reads it, consider `&str`\n    <- `main` (Clone)\n    <- `main` (Move)"));
+        assert!(output.contains("`total` takes `values: std::vec::Vec<u32>` by value but only reads it, consider `&[u32]`\n    <- `main` (Move)"));
+        assert!(output.contains("`is_verbose` takes `config: Config` by value but only reads it, consider `&Config`\n    <- `main` (Clone)"));
+        assert!(output.contains("`Config::describe` takes `self: Config` by value but only reads it, consider `&self`\n    <- `main` (Clone)"));
+        // The parameters which are moved, mutated or stored, or which are references or `Copy`.
+        assert!(!output.contains("`Config::into_name`"));
+        assert!(!output.contains("`keep`"));
+        assert!(!output.contains("`rename`"));
+        assert!(!output.contains("`by_ref`"));
+        assert!(!output.contains("`copy`"));
+
+        Ok(())
+    }
+}
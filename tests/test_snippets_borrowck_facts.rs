@@ -0,0 +1,51 @@
+mod utils;
+
+mod test_snippets_borrowck_facts {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/borrowck_facts";
+
+    #[test]
+    fn test_ownership_metrics() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/ownership_metrics.rs")).unwrap();
+        let (output, _) =
+            run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph", "--borrowck-facts"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("temp::shared) - None - borrowck: 1 shared, 0 mut, 0 reborrows, 0 two-phase, 0 moves, 0 outliving\"]"));
+        assert!(output.contains("temp::push_len) - None - borrowck: 1 shared, 1 mut, 2 reborrows, 1 two-phase, 0 moves, 0 outliving\"]"));
+        assert!(output.contains("temp::consume) - None - borrowck: 0 shared, 0 mut, 0 reborrows, 0 two-phase, 3 moves, 0 outliving\"]"));
+        assert!(output.contains("temp::local) - None - borrowck: 0 shared, 1 mut, 0 reborrows, 0 two-phase, 0 moves, 0 outliving\"]"));
+        assert!(output.contains("temp::array::{closure#0}) - None - Fn [] - borrowck: 0 shared, 0 mut, 0 reborrows, 0 two-phase, 0 moves, 0 outliving\"]"));
+        assert!(output.contains("temp::first_mut) - None - borrowck: 0 shared, 3 mut, 3 reborrows, 0 two-phase, 0 moves, 3 outliving\"]"));
+        assert!(output.contains("temp::leak) - None - borrowck: 0 shared, 2 mut, 2 reborrows, 0 two-phase, 0 moves, 2 outliving\"]"));
+        // The constants have no borrow checker facts.
+        assert!(output.contains("temp::array::{constant#0}) - None\"]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_const_fn() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/const_fn.rs")).unwrap();
+        let (output, _) =
+            run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph", "--borrowck-facts"])?;
+        let output = strip_crate_hashes(&output);
+
+        // The body of `len` is borrow checked when the array length of `buffer` is evaluated,
+        // before the analysis reaches it.
+        assert!(output.contains("temp::len) - None - borrowck: 0 shared, 1 mut, 0 reborrows, 0 two-phase, 0 moves, 0 outliving\"]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ownership_metrics_without_flag() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/ownership_metrics.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(!output.contains("borrowck:"));
+
+        Ok(())
+    }
+}
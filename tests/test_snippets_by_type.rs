@@ -0,0 +1,39 @@
+mod utils;
+
+mod test_snippets_by_type {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/by_type";
+
+    #[test]
+    fn test_argument_usages() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/argument_usages.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--by-type"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("`Account`: 1 Move, 2 Clone, 3 Borrow, 3 MutBorrow\n"));
+        assert!(output
+            .contains("    Move: DefId(0:7 ~ temp::main) -> DefId(0:3 ~ temp::consume) (1)\n")); // main -> consume
+        assert!(output
+            .contains("    Clone: DefId(0:7 ~ temp::main) -> DefId(0:3 ~ temp::consume) (2)\n")); // main -> consume
+        assert!(output
+            .contains("    Borrow: DefId(0:7 ~ temp::main) -> DefId(0:4 ~ temp::inspect) (3)\n")); // main -> inspect
+        assert!(output.contains(
+            "    MutBorrow: DefId(0:7 ~ temp::main) -> DefId(0:5 ~ temp::deposit) (3)\n"
+        )); // main -> deposit
+            // The moves of the temporaries of `Copy` types are copies.
+        assert!(output.contains("`u64`: 1 Copy, 2 Constant\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_argument_types_on_edges() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/argument_usages.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("arg_tys: [RLArgTy { ty: \\\"Account\\\", usage: MutBorrow }, RLArgTy { ty: \\\"u64\\\", usage: Constant }]")); // main -> deposit
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+mod utils;
+
+mod test_snippets_call_source {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/call_source";
+
+    #[test]
+    fn test_implicit_calls() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/implicit_calls.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("call_source: User")); // w.len()
+        assert!(output.contains("call_source: Operator")); // Meters(1) + Meters(2)
+        assert!(output.contains("call_source: QuestionMark")); // s.parse::<u32>()?
+        assert!(output.contains("call_source: ForLoop")); // for x in vec![1, 2, 3]
+        assert!(output.contains("call_source: Desugaring")); // drop(w)
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_implicit_calls_hidden() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/implicit_calls.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &[
+                "--print-rl-graph",
+                "--hide-call-source",
+                "operator",
+                "--hide-call-source",
+                "question-mark",
+                "--hide-call-source",
+                "for-loop",
+            ],
+        )?;
+
+        assert!(output.contains("call_source: User"));
+        assert!(output.contains("call_source: Desugaring"));
+        assert!(!output.contains("call_source: Operator"));
+        assert!(!output.contains("call_source: QuestionMark"));
+        assert!(!output.contains("call_source: ForLoop"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,23 @@
+mod utils;
+
+mod test_snippets_closure_captures {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/closure_captures";
+
+    #[test]
+    fn test_capture_by_value_ref_mut_ref() -> Result<(), String> {
+        let snippet =
+            &std::fs::read_to_string(format!("{FOLDER}/capture_by_value_ref_mut_ref.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("FnOnce [by_value: Move]")); // main::{{closure#0}}
+        assert!(output.contains("Fn [by_ref: Ref]")); // main::{{closure#1}}
+        assert!(output.contains("FnMut [by_mut_ref: MutRef]")); // main::{{closure#2}}
+        assert!(output.contains("0 -> 2")); // main -> main::{{closure#0}} (FnOnce::call_once)
+        assert!(output.contains("0 -> 3")); // main -> main::{{closure#1}} (Fn::call)
+        assert!(output.contains("0 -> 4")); // main -> main::{{closure#2}} (FnMut::call_mut)
+
+        Ok(())
+    }
+}
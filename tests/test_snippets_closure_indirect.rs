@@ -0,0 +1,41 @@
+mod utils;
+
+mod test_snippets_closure_indirect {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/closure_indirect";
+
+    #[test]
+    fn test_iterator_map() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/iterator_map.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("0 -> 4")); // main -> main::{{closure}} (Indirect)
+        assert!(output.contains("via: Some(\\\"std::iter::Iterator::map\\\")"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_option_map_or_else() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/option_map_or_else.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("1 -> 2")); // main -> main::{{closure}} (Indirect)
+        assert!(output.contains("1 -> 0")); // main -> double (Indirect)
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_thread_spawn() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/thread_spawn.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("0 -> 1")); // main -> main::{{closure}} (Indirect)
+        assert!(output.contains("0 -> 2")); // main -> std::thread::spawn
+        assert!(output.contains("via: Some(\\\"std::thread::spawn\\\")"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,22 @@
+mod utils;
+
+mod test_snippets_control_flow {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/control_flow";
+
+    #[test]
+    fn test_nested_loops() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/nested_loops.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("5 -> 0 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false, loop_depth: 0, cleanup: false, conditional: false")); // main -> once
+        assert!(output.contains("5 -> 1 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false, loop_depth: 1, cleanup: false, conditional: true")); // main -> inner
+        assert!(output.contains("5 -> 2 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false, loop_depth: 2, cleanup: false, conditional: true")); // main -> innermost
+        assert!(output.contains("5 -> 3 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false, loop_depth: 1, cleanup: false, conditional: true")); // main -> maybe
+        assert!(output.contains("5 -> 4 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false, loop_depth: 0, cleanup: false, conditional: false")); // main -> after
+        assert!(output.contains("ty: \\\"std::vec::Vec<u32>\\\" }), call_source: Desugaring, unsafe_context: false, loop_depth: 0, cleanup: true, conditional: true")); // main -> <Vec<u32> as Drop>::drop
+
+        Ok(())
+    }
+}
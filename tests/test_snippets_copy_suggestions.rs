@@ -0,0 +1,52 @@
+mod utils;
+
+mod test_snippets_copy_suggestions {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/copy_suggestions";
+
+    #[test]
+    fn test_small_cloned_types() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/small_cloned_types.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--suggest-copy"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("`Point` (8 bytes) is cloned at 4 call sites, consider deriving `Copy`\n    DefId(0:5 ~ temp::main) (3)\n    DefId(0:7 ~ temp::other) (1)"));
+        assert!(output.contains(
+            "`Borrowed<'_>` (8 bytes) is cloned at 2 call sites, consider deriving `Copy`"
+        ));
+        // Cloned only once.
+        assert!(!output.contains("`Direction`"));
+        // Larger than the default threshold.
+        assert!(!output.contains("`Large`"));
+        // A field is not `Copy`.
+        assert!(!output.contains("`Named`"));
+        // It is already `Copy`.
+        assert!(!output.contains("`AlreadyCopy`"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_small_cloned_types_with_thresholds() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/small_cloned_types.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &[
+                "--suggest-copy",
+                "--copy-min-clones",
+                "1",
+                "--copy-max-size",
+                "64",
+            ],
+        )?;
+
+        assert!(output
+            .contains("`Direction` (1 bytes) is cloned at 1 call sites, consider deriving `Copy`"));
+        assert!(output
+            .contains("`Large` (64 bytes) is cloned at 2 call sites, consider deriving `Copy`"));
+        assert!(!output.contains("`Named`"));
+
+        Ok(())
+    }
+}
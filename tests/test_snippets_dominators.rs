@@ -0,0 +1,20 @@
+mod utils;
+
+mod test_snippets_dominators {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/dominators";
+
+    #[test]
+    fn test_api_boundary() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/api_boundary.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-dominators"])?;
+        let output = strip_crate_hashes(&output);
+
+        // `shared` is reached through both `tokenize` and `render`, so only `api` dominates it.
+        assert!(output.contains("Entry point DefId(0:3 ~ temp::api) (Public):\n    DefId(0:3 ~ temp::api) (dominates 5)\n        DefId(0:4 ~ temp::parse) (dominates 2)\n            DefId(0:5 ~ temp::tokenize)\n            DefId(0:6 ~ temp::validate)\n        DefId(0:7 ~ temp::render)\n        DefId(0:8 ~ temp::shared)\n"));
+        assert!(output.contains("Entry point DefId(0:7 ~ temp::render) (Public):\n    DefId(0:7 ~ temp::render) (dominates 1)\n        DefId(0:8 ~ temp::shared)\n"));
+
+        Ok(())
+    }
+}
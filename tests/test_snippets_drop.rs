@@ -0,0 +1,41 @@
+mod utils;
+
+mod test_snippets_drop {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/drop";
+
+    #[test]
+    fn test_drop_impl() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/drop_impl.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("1 -> 0")); // consume -> <T as Drop>::drop
+        assert!(output.contains("2 -> 0")); // main -> <T as Drop>::drop
+        assert!(output.contains("2 -> 4")); // main -> <Vec<T> as Drop>::drop
+        assert!(output.contains("ty: \\\"Wrapper\\\" }), call_source: Desugaring, unsafe_context: false, loop_depth: 0, cleanup: false, conditional: false"));
+        assert!(output.contains("ty: \\\"T\\\" }), call_source: Desugaring, unsafe_context: false, loop_depth: 0, cleanup: false, conditional: true"));
+        assert!(output.contains("cleanup: true, conditional: true"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_drop_guards() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/drop_guards.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        // The guards and the references only borrow the `Wrapper`, so they do not drop it.
+        assert!(!output.contains("\n    1 -> 0 ")); // read -> <Wrapper as Drop>::drop
+        assert!(!output.contains("\n    4 -> 0 ")); // write -> <Wrapper as Drop>::drop
+        assert!(!output.contains("\n    7 -> 0 ")); // lock -> <Wrapper as Drop>::drop
+        assert!(!output.contains("\n    11 -> 0 ")); // view -> <Wrapper as Drop>::drop
+        assert!(output.contains("\n    7 -> 10 ")); // lock -> <MutexGuard<T> as Drop>::drop
+                                                    // The containers own the `Wrapper`.
+        assert!(output.contains("\n    12 -> 0 ")); // owned -> <Wrapper as Drop>::drop
+        assert!(output.contains("ty: \\\"std::sync::Mutex<Wrapper>\\\" })"));
+        assert!(output.contains("ty: \\\"std::cell::RefCell<Wrapper>\\\" })"));
+
+        Ok(())
+    }
+}
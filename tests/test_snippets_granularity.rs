@@ -0,0 +1,56 @@
+mod utils;
+
+mod test_snippets_granularity {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/granularity";
+
+    #[test]
+    fn test_modules_impl_granularity() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/modules.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-rl-graph", "--granularity", "impl"],
+        )?;
+
+        // `new`, `area` and `Display::fmt` are collapsed into `Circle`.
+        assert!(output.contains("0 [ label=\"temp::shapes::Circle (3 functions)\"]"));
+        // The closure is collapsed into the function defining it.
+        assert!(output.contains("5 [ label=\"temp::report::describe (2 functions)\"]"));
+        assert!(output.contains("10 -> 0 [ label=\"calls: 2, ownership score: 6\"]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_modules_module_granularity() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/modules.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-rl-graph", "--granularity", "module"],
+        )?;
+
+        assert!(output.contains("0 [ label=\"temp::shapes (4 functions)\"]"));
+        assert!(output.contains("3 [ label=\"temp::report (2 functions)\"]"));
+        assert!(output.contains("7 [ label=\"temp (1 functions)\"]"));
+        assert!(output.contains("0 -> 0 [ label=\"calls: 2, ownership score: 7\"]"));
+        assert!(output.contains("3 -> 0 [ label=\"calls: 1, ownership score: 2\"]"));
+        assert!(output.contains("7 -> 3 [ label=\"calls: 1, ownership score: 9\"]"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_modules_crate_granularity() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/modules.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-serialized-rl-graph", "--granularity", "crate"],
+        )?;
+
+        assert!(output.contains("{\"nodes\":[{\"label\":\"temp\",\"functions\":7},{\"label\":\"core\",\"functions\":6},{\"label\":\"alloc\",\"functions\":5}]"));
+        assert!(output.contains("[0,0,{\"calls\":7,\"ownership_score\":25}]"));
+
+        Ok(())
+    }
+}
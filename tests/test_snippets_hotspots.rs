@@ -0,0 +1,46 @@
+mod utils;
+
+mod test_snippets_hotspots {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/hotspots";
+
+    #[test]
+    fn test_ownership_hub() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/ownership_hub.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--hotspots", "5"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("Top 5 hotspots of the crate `temp`:"));
+        // `process` is the only function of the crate between the entry points and `store`.
+        assert!(output.contains("DefId(0:8 ~ temp::process) (crate `temp`): PageRank 0.0948, betweenness 0.1212, in-degree 27, out-degree 21"));
+        assert!(output.contains("(crate `alloc`)"));
+        assert!(!output.contains("temp::main)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_ownership_hub_top_1() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/ownership_hub.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--hotspots", "1"])?;
+
+        assert_eq!(output.matches("PageRank").count(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_weighted_betweenness() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/weighted_paths.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--hotspots", "10"])?;
+        let output = strip_crate_hashes(&output);
+
+        // The shortest paths from `api` to `target` go through the calls moving the data.
+        assert!(output.contains("temp::heavy) (crate `temp`): PageRank 0.1227, betweenness 0.1000"));
+        // Only the path from `api` to `Vec::new` goes through `light`.
+        assert!(output.contains("temp::light) (crate `temp`): PageRank 0.0949, betweenness 0.0333"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,24 @@
+mod utils;
+
+mod test_snippets_interior_mutability {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/interior_mutability";
+
+    #[test]
+    fn test_refcell_mutex_atomic() -> Result<(), String> {
+        let snippet =
+            &std::fs::read_to_string(format!("{FOLDER}/refcell_mutex_atomic.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("push) - None - runtime borrows: 0 shared, 1 mut"));
+        assert!(output.contains("len) - None - runtime borrows: 1 shared, 0 mut"));
+        assert!(output.contains("lock_and_add) - None - runtime borrows: 0 shared, 1 mut"));
+        assert!(output.contains("17 -> 0 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [(Copy, Interior, Unknown), (Constant, Not, Primitive)]")); // main -> push
+        assert!(output.contains("17 -> 8 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [(Copy, Interior, Unknown), (Constant, Not, Primitive)]")); // main -> lock_and_add
+        assert!(output.contains("17 -> 12 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [(Copy, Interior, Unknown)]")); // main -> bump
+        assert!(output.contains("17 -> 14 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [(Copy, Not, Unknown)]")); // main -> sum
+
+        Ok(())
+    }
+}
@@ -0,0 +1,46 @@
+mod utils;
+
+mod test_snippets_layering {
+    use crate::utils::{run_with_cargo_bin_and_snippet, run_with_cargo_bin_and_snippet_failing};
+
+    const FOLDER: &str = "tests/snippets/layering";
+
+    #[test]
+    fn test_layers() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/layers.rs")).unwrap();
+        let rules = std::fs::canonicalize(format!("{FOLDER}/rules.toml")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--layering-rules", rules.to_str().unwrap()],
+        )?;
+
+        assert!(output.contains("`the domain must not call the infrastructure`: temp::domain::place_order -> temp::infra::save (src/lib.rs:8:9: 8:35)"));
+        assert!(output.contains("`only the adapters can call the legacy code`: temp::api::handle -> temp::legacy::old_format (src/lib.rs:48:9: 48:38)"));
+        // Allowed by `except`.
+        assert!(!output.contains("temp::adapters::format -> temp::legacy::old_format"));
+        // A call inside the legacy code.
+        assert!(!output.contains("temp::legacy::old_format -> temp::legacy::pad"));
+        // The infrastructure can call the domain.
+        assert!(!output.contains("temp::infra::save -> temp::domain::validate"));
+        assert!(
+            output.contains("Module cycles of the crate `temp`:\ntemp::domain <-> temp::infra\n")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_misspelled_rule_key() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/layers.rs")).unwrap();
+        let rules = std::fs::canonicalize(format!("{FOLDER}/misspelled_rules.toml")).unwrap();
+        let stderr = run_with_cargo_bin_and_snippet_failing(
+            snippet,
+            &["--layering-rules", rules.to_str().unwrap()],
+        )?;
+
+        assert!(stderr.contains("error: Failed to parse the rule file"));
+        assert!(stderr.contains("unknown field `exept`"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,33 @@
+mod utils;
+
+mod test_snippets_ownership {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/ownership";
+
+    #[test]
+    fn test_buffer_chain() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/buffer_chain.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-ownership-chains"])?;
+
+        assert!(output.contains("`Buffer` (length 5): pipeline::buf -> fill::buf -> pipeline::filled -> sink::buf -> sink::owned -> dropped"));
+        assert!(output.contains("`Buffer` (length 2): store::buf -> keep::buf -> stored"));
+        assert!(output.contains("`Buffer` (length 1): local::buf -> dropped"));
+        assert!(
+            output.contains("`std::vec::Vec<Buffer>` (length 1): external::buffers -> returned")
+        );
+        // The temporaries are not owners.
+        assert!(output.contains("`Buffer` (length 0): moved into `std::vec::Vec::<T, A>::push`"));
+        assert!(!output.contains("external::_"));
+        // The value returned by `fill` is returned by `create` too.
+        assert!(output.contains("`Buffer` (length 2): create::buf -> fill::buf -> returned"));
+        // The arguments of a closure are tupled, so they are not followed into its body.
+        assert!(
+            output.contains("`Buffer` (length 1): apply::buf -> moved into `std::ops::Fn::call`")
+        );
+        // The values created in the return place are followed from the caller.
+        assert!(!output.contains("Buffer::new::_0"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+mod utils;
+
+mod test_snippets_reachability {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/reachability";
+
+    #[test]
+    fn test_entry_points() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/entry_points.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-unreachable"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("Entry points of the crate `temp`:\nDefId(0:3 ~ temp::api) (Public)\nDefId(0:9 ~ temp::exported) (Export)\n"));
+        // The call through the function pointer is not resolved.
+        assert!(output.contains("Unreachable functions of the crate `temp`:\nDefId(0:7 ~ temp::through_pointer)\nDefId(0:11 ~ temp::dead)\nDefId(0:12 ~ temp::dead_callee)\nDefId(0:16 ~ temp::{impl#0}::dead_method)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry_points_over_approximate_unknown_calls() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/entry_points.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-unreachable", "--over-approximate-unknown-calls"],
+        )?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("Unreachable functions of the crate `temp`:\nDefId(0:11 ~ temp::dead)\nDefId(0:12 ~ temp::dead_callee)\nDefId(0:16 ~ temp::{impl#0}::dead_method)\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_entry_points_with_tests() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/entry_points.rs")).unwrap();
+        let (output, _) =
+            run_with_cargo_bin_and_snippet(snippet, &["--print-unreachable", "--", "--tests"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("temp::tests::it_works#1) (Test)"));
+        assert!(!output.contains("temp::tests::tested)"));
+
+        Ok(())
+    }
+}
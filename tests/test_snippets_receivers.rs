@@ -0,0 +1,37 @@
+mod utils;
+
+mod test_snippets_receivers {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/receivers";
+
+    #[test]
+    fn test_receiver_kinds() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/receiver_kinds.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-receivers"])?;
+
+        assert!(output.contains("    self: `Counter::total` (calls: 2, cloned: 2), `Counter::into_value` (calls: 1, cloned: 0), `<Counter as Describe>::describe` (calls: 1, cloned: 1)\n"));
+        assert!(output.contains("    &self: `<Counter as std::clone::Clone>::clone` (calls: 4, cloned: 0), `Counter::get` (calls: 1, cloned: 0)\n"));
+        assert!(output.contains("    &mut self: `Counter::increment` (calls: 1, cloned: 0)\n"));
+        assert!(output.contains("    other: `Counter::boxed` (calls: 1, cloned: 0)\n"));
+        assert!(output.contains("    `Counter::total` takes `self` but it is always called on a clone, consider `&self`"));
+        assert!(output.contains("    `<Counter as Describe>::describe` takes `self` but it is always called on a clone, consider `&self`"));
+        assert!(!output.contains("`Counter::into_value` takes `self`"));
+        // The associated functions without `self` are not methods.
+        assert!(!output.contains("`Counter::new`"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_same_path_adts() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/same_path_adts.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-receivers"])?;
+
+        // The two `Local` are printed with the same path, but their calls are counted apart.
+        assert!(output.contains("`main::Local`\n    self: `<main::Local as Describe>::describe` (calls: 1, cloned: 1)\n    &self: `<main::Local as std::clone::Clone>::clone` (calls: 1, cloned: 0)\n"));
+        assert!(output.contains("`main::Local`\n    self: `<main::Local as Describe>::describe` (calls: 1, cloned: 0)\n    &self: `<main::Local as std::clone::Clone>::clone` (calls: 0, cloned: 0)\n"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+mod utils;
+
+mod test_snippets_rules {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/rules";
+
+    #[test]
+    fn test_metrics() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/metrics.rs")).unwrap();
+        let rules = std::fs::canonicalize(format!("{FOLDER}/rules.toml")).unwrap();
+        let (output, _) =
+            run_with_cargo_bin_and_snippet(snippet, &["--rules", rules.to_str().unwrap()])?;
+
+        assert!(output.contains("Rule findings of the crate `temp`:"));
+        assert!(output.contains("error: a composite value must not be moved into the metrics: temp::handlers::handle -> temp::metrics::record (src/lib.rs:26:45: 26:68)"));
+        assert!(output.contains("warning: the metrics must not call the standard library: temp::metrics::inspect -> alloc::string::{impl#0}::len (src/lib.rs:16:9: 16:26)"));
+        // A primitive moved into the metrics.
+        assert!(!output.contains("temp::handlers::handle -> temp::metrics::record_value"));
+        // A composite borrowed by the metrics.
+        assert!(!output.contains("temp::handlers::handle -> temp::metrics::inspect"));
+        // No caller in the crate `other`.
+        assert!(!output.contains("note:"));
+
+        Ok(())
+    }
+}
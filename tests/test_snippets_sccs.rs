@@ -0,0 +1,39 @@
+mod utils;
+
+mod test_snippets_sccs {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/sccs";
+
+    #[test]
+    fn test_recursion() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/recursion.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-sccs"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("SCC with 1 functions:\n    DefId(0:3 ~ temp::factorial)\n    DefId(0:3 ~ temp::factorial) -> DefId(0:3 ~ temp::factorial)"));
+        assert!(output.contains("SCC with 2 functions:\n    DefId(0:4 ~ temp::is_even)\n    DefId(0:5 ~ temp::is_odd)\n    DefId(0:4 ~ temp::is_even) -> DefId(0:5 ~ temp::is_odd)\n    DefId(0:5 ~ temp::is_odd) -> DefId(0:4 ~ temp::is_even)"));
+        assert!(output.contains("SCC with 2 functions:\n    DefId(0:6 ~ temp::walk)\n    DefId(0:7 ~ temp::walk::{closure#0})"));
+        // The call through the function pointer is not resolved.
+        assert!(!output.contains("temp::countdown)\n"));
+        assert!(!output.contains("temp::leaf)"));
+        assert!(!output.contains("temp::main)"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_recursion_over_approximate_unknown_calls() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/recursion.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-sccs", "--over-approximate-unknown-calls"],
+        )?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("SCC with 3 functions:\n    DefId(0:8 ~ temp::apply)\n    STATICALLY_UNKNOWN ~ temp::apply@bb0[0]: fn(u32) -> u32\n    DefId(0:9 ~ temp::countdown)"));
+        assert!(!output.contains("temp::leaf)"));
+
+        Ok(())
+    }
+}
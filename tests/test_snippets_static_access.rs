@@ -0,0 +1,36 @@
+mod utils;
+
+mod test_snippets_static_access {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/static_access";
+
+    #[test]
+    fn test_read_write_statics() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/read_write_statics.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-rl-graph", "--track-static-accesses"],
+        )?;
+
+        assert!(output.contains("16 -> 0 [ label=\"RLEdge { call_multiplier: Reads")); // read_limit -> LIMIT
+        assert!(output.contains("17 -> 1 [ label=\"RLEdge { call_multiplier: Reads")); // increment -> COUNTER
+        assert!(output.contains("17 -> 1 [ label=\"RLEdge { call_multiplier: Writes")); // increment -> COUNTER
+        assert!(output.contains("18 -> 2 [ label=\"RLEdge { call_multiplier: Reads")); // hit -> HITS
+        assert!(output.contains("20 -> 4 [ label=\"RLEdge { call_multiplier: Reads")); // enter -> DEPTH
+        assert!(output.contains("25 -> 4 [ label=\"RLEdge { call_multiplier: Writes")); // reset -> DEPTH
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_write_statics_not_tracked() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/read_write_statics.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(!output.contains("call_multiplier: Reads"));
+        assert!(!output.contains("call_multiplier: Writes"));
+
+        Ok(())
+    }
+}
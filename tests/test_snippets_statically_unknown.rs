@@ -0,0 +1,53 @@
+mod utils;
+
+mod test_snippets_statically_unknown {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/statically_unknown";
+
+    #[test]
+    fn test_call_fn_ptr_args_one_node_per_call_site() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/call_fn_ptr_args.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("0 -> 1")); // apply -> STATICALLY_UNKNOWN (apply@bb0[0])
+        assert!(output.contains("2 -> 3")); // apply_twice -> STATICALLY_UNKNOWN (apply_twice@bb0[1])
+        assert!(output.contains("2 -> 4")); // apply_twice -> STATICALLY_UNKNOWN (apply_twice@bb1[1])
+        assert!(output.contains("fn(T) -> ()"));
+        assert!(!output.contains("OverApproximated"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_fn_ptr_args_over_approximated() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/call_fn_ptr_args.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-rl-graph", "--over-approximate-unknown-calls"],
+        )?;
+
+        assert!(output.contains("1 -> 5")); // STATICALLY_UNKNOWN (apply@bb0[0]) -> test
+        assert!(output.contains("3 -> 5")); // STATICALLY_UNKNOWN (apply_twice@bb0[1]) -> test
+        assert!(output.contains("4 -> 5")); // STATICALLY_UNKNOWN (apply_twice@bb1[1]) -> test
+        assert!(!output.contains("-> 6 [")); // test_other has a different signature
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_call_closure_over_approximated() -> Result<(), String> {
+        let snippet =
+            &std::fs::read_to_string("tests/snippets/call_with_no_args/monomorphized_args.rs")
+                .unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &["--print-rl-graph", "--over-approximate-unknown-calls"],
+        )?;
+
+        assert!(output.contains("0 -> 1")); // outline -> STATICALLY_UNKNOWN
+        assert!(output.contains("1 -> 3")); // STATICALLY_UNKNOWN -> main::{{closure}}
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+mod utils;
+
+mod test_snippets_transitive_cost {
+    use crate::utils::{run_with_cargo_bin_and_snippet, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/snippets/transitive_cost";
+
+    #[test]
+    fn test_public_api() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/public_api.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--transitive-cost", "4"])?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("Top 4 transitive ownership costs of the crate `temp`:"));
+        // The clone and the moved composite of `prepare`, plus the cost of its callees.
+        assert!(output.contains("temp::prepare): transitive cost 20.00, own cost 15"));
+        assert!(output.contains("temp::api): transitive cost 32.00, own cost 3"));
+        // The mutually recursive functions share the cost of their component.
+        assert!(output.contains("temp::ping): transitive cost 9.00, own cost 7"));
+        assert!(output.contains("temp::pong): transitive cost 9.00, own cost 2"));
+        assert!(!output.contains("temp::leaf): transitive cost"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_public_api_damping() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/public_api.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(
+            snippet,
+            &[
+                "--transitive-cost",
+                "2",
+                "--cost-damping",
+                "0.5",
+                "--print-rl-graph",
+            ],
+        )?;
+        let output = strip_crate_hashes(&output);
+
+        assert!(output.contains("Top 2 transitive ownership costs of the crate `temp`:\nDefId(0:5 ~ temp::prepare): transitive cost 17.50, own cost 15\nDefId(0:8 ~ temp::api): transitive cost 16.25, own cost 3\n"));
+        // The costs are shown on the nodes of the graph.
+        assert!(output.contains("temp::leaf) - None - transitive cost 0.00, own cost 0\"]"));
+
+        Ok(())
+    }
+}
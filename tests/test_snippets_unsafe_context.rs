@@ -0,0 +1,35 @@
+mod utils;
+
+mod test_snippets_unsafe_context {
+    use crate::utils::run_with_cargo_bin_and_snippet;
+
+    const FOLDER: &str = "tests/snippets/unsafe_context";
+
+    #[test]
+    fn test_unsafe_context() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/unsafe_surface.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-rl-graph"])?;
+
+        assert!(output.contains("raw_increment) - None - unsafe fn"));
+        assert!(output.contains("read_counter) - None - unsafe blocks: 1"));
+        assert!(output.contains("3 -> 1 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: true")); // safe_increment -> raw_increment
+        assert!(output.contains("4 -> 3 [ label=\"RLEdge { call_multiplier: Function, arg_weights: [], via: None, drop: None, call_source: User, unsafe_context: false")); // indirect_increment -> safe_increment
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsafe_surface() -> Result<(), String> {
+        let snippet = &std::fs::read_to_string(format!("{FOLDER}/unsafe_surface.rs")).unwrap();
+        let (output, _) = run_with_cargo_bin_and_snippet(snippet, &["--print-unsafe-surface"])?;
+
+        assert!(output.contains("safe_increment)\n"));
+        assert!(output.contains("indirect_increment)\n"));
+        assert!(output.contains("total)\n"));
+        assert!(output.contains("raw_increment) (unsafe fn)"));
+        assert!(output.contains("read_counter) (unsafe block)"));
+        assert!(!output.contains("pure)")); // it does not reach unsafe code
+
+        Ok(())
+    }
+}
@@ -16,7 +16,7 @@ mod test_workspaces {
     #[test]
     fn test_help_output() -> Result<(), String> {
         let (output, _) = run_with_cargo_bin(FOLDER, None, &["--help"])?;
-        for options in &["--print-crate"] {
+        for options in &["--print-crate", "--over-approximate-unknown-calls"] {
             assert!(output.contains(options));
         }
         Ok(())
@@ -0,0 +1,143 @@
+mod utils;
+
+mod test_workspaces_clone_chain {
+    use crate::utils::run_with_cargo_bin;
+    use std::path::Path;
+    use std::process::Command;
+
+    const FOLDER: &str = "tests/workspaces/clone_chain";
+
+    /// Copy the sources of the workspace, without its build artifacts and graphs.
+    fn copy_workspace(from: &Path, to: &Path) {
+        std::fs::create_dir_all(to).unwrap();
+        for entry in std::fs::read_dir(from).unwrap() {
+            let entry = entry.unwrap();
+            let name = entry.file_name();
+            if name == "target" || name == ".rl_serde" {
+                continue;
+            }
+            if entry.file_type().unwrap().is_dir() {
+                copy_workspace(&entry.path(), &to.join(&name));
+            } else {
+                std::fs::copy(entry.path(), to.join(&name)).unwrap();
+            }
+        }
+    }
+
+    fn git(workspace: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args([
+                "-c",
+                "user.name=rusty-links",
+                "-c",
+                "user.email=rusty-links@test",
+            ])
+            .args(args)
+            .current_dir(workspace)
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn test_workspaces_clone_chain_paths() -> Result<(), String> {
+        // The paths are queried on the merged graph of the analysis.
+        run_with_cargo_bin(FOLDER, None, &[])?;
+
+        let (output, _) = run_with_cargo_bin(
+            FOLDER,
+            None,
+            &[
+                "path",
+                "--from",
+                "clone_chain::main",
+                "--to",
+                "storage::persist",
+                "--k",
+                "3",
+            ],
+        )?;
+        assert!(output.contains("Cheapest paths from `clone_chain::main` to `storage::persist`:\nPath 1 (ownership score 9):\n    clone_chain::main -> storage::persist (src/main.rs:6:5: 6:29): ownership score 9, Function [storage::Record Move (Move, Mut, Composite)]\nPath 2 (ownership score 10):\n"));
+        assert!(output.contains("    service::transform -> storage::persist (crates/service/src/lib.rs:12:5: 12:27): ownership score 5, Function [storage::Record Move (Move, Not, Composite)]\n"));
+        assert!(!output.contains("Path 3"));
+
+        let (output, _) = run_with_cargo_bin(
+            FOLDER,
+            None,
+            &[
+                "path",
+                "--from",
+                "clone_chain::main",
+                "--to",
+                "storage::persist",
+                "--most-expensive",
+            ],
+        )?;
+        assert!(output.contains("Most expensive paths from `clone_chain::main` to `storage::persist`:\nPath 1 (ownership score 10):\n    clone_chain::main -> service::process (src/main.rs:5:5: 5:34): ownership score 3, Function [storage::Record MutBorrow (Copy, Mut, Unknown)]\n    service::process -> service::transform"));
+        assert!(!output.contains("Path 2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspaces_clone_chain_ownership_chains() -> Result<(), String> {
+        let (output, _) = run_with_cargo_bin(FOLDER, None, &["--print-ownership-chains"])?;
+
+        // In the crates, the chains stop at the calls of the other crates.
+        assert!(output.contains("Ownership chains of the crate `clone_chain`:\n`storage::Record` (length 1): main::record -> moved into `storage::persist`\n"));
+        // In the workspace, they follow the values into the other crates.
+        assert!(output.contains("Ownership chains of the workspace:\n`storage::Record` (length 2): main::record -> persist::record -> dropped\n"));
+        assert!(output.contains(
+            "`storage::Record` (length 2): transform::copy -> persist::record -> dropped\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspaces_clone_chain_crate_granularity() -> Result<(), String> {
+        let (output, _) = run_with_cargo_bin(
+            FOLDER,
+            None,
+            &["--print-rl-graph", "--granularity", "crate"],
+        )?;
+
+        // The graph of the workspace is printed after the ones of its crates.
+        let workspace_graph = output.rsplit("digraph").next().unwrap();
+        assert!(workspace_graph.contains(
+            "    2 [ label=\"service (2 functions)\"]\n    3 [ label=\"storage (1 functions)\"]\n"
+        ));
+        // The calls from `service` to `storage`, known only by the crate `service`.
+        assert!(workspace_graph.contains("    2 -> 3 [ label=\"calls: 1, ownership score: 5\"]\n"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_workspaces_clone_chain_impact() -> Result<(), String> {
+        // The workspace is copied into a new git repository, where a function is changed.
+        let workspace = std::env::temp_dir().join("rusty-links-impact-clone-chain");
+        let _ = std::fs::remove_dir_all(&workspace);
+        copy_workspace(Path::new(FOLDER), &workspace);
+        git(&workspace, &["init", "-q"]);
+        git(&workspace, &["add", "-A"]);
+        git(&workspace, &["commit", "-q", "-m", "init"]);
+        let lib_rs = workspace.join("crates/service/src/lib.rs");
+        let source = std::fs::read_to_string(&lib_rs).unwrap();
+        std::fs::write(&lib_rs, source.replace("push('!')", "push('?')")).unwrap();
+
+        let workspace_str = workspace.to_str().unwrap();
+        run_with_cargo_bin(workspace_str, None, &["--", "--tests"])?;
+        let (output, _) = run_with_cargo_bin(workspace_str, None, &["impact", "--since", "HEAD"])?;
+        std::fs::remove_dir_all(&workspace).unwrap();
+
+        assert!(output.contains("Changed functions since `HEAD`:\nservice::transform (crates/service/src/lib.rs:7-13)\n"));
+        assert!(output.contains("Crate `clone_chain`:\n    clone_chain::main\n"));
+        assert!(output.contains("Crate `service`:\n    service::process\n    service::transform\n"));
+        assert!(output.contains("Affected tests since `HEAD`:\npipeline::test_process\n"));
+        // The test which does not call the changed function.
+        assert!(!output.contains("pipeline::test_persist"));
+
+        Ok(())
+    }
+}
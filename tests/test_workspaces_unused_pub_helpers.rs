@@ -0,0 +1,25 @@
+mod utils;
+
+mod test_workspaces_unused_pub_helpers {
+    use crate::utils::{run_with_cargo_bin, strip_crate_hashes};
+
+    const FOLDER: &str = "tests/workspaces/unused_pub_helpers";
+
+    #[test]
+    fn test_workspaces_unused_pub_helpers() -> Result<(), String> {
+        let (output, _) = run_with_cargo_bin(FOLDER, None, &["--print-unreachable"])?;
+        let output = strip_crate_hashes(&output);
+
+        // In its own crate, a public function is an entry point.
+        assert!(output.contains(
+            "Unreachable functions of the crate `crate_a`:\nDefId(0:7 ~ crate_a::private_dead)\n"
+        ));
+        // In the workspace, only the public functions called by the other crates are reachable.
+        assert!(output.contains(
+            "Entry points of the workspace:\nDefId(0:3 ~ unused_pub_helpers::main) (Main)\n"
+        ));
+        assert!(output.contains("Unreachable functions of the workspace:\nDefId(0:5 ~ crate_a::unused_helper)\nDefId(0:6 ~ crate_a::private_helper)\nDefId(0:7 ~ crate_a::private_dead)\n"));
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use std::env;
 use std::fs;
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Output};
 use std::sync::Once;
 
 const PLUGIN_NAME: &str = "rusty-links";
@@ -25,6 +25,28 @@ pub fn run_with_cargo_bin(
     expected_outout_name: Option<&str>,
     plugin_args: &[&str],
 ) -> Result<(String, Option<String>), String> {
+    let output = run_plugin(cargo_project_name, plugin_args);
+    assert!(output.status.success());
+
+    if let Some(expected_outout_name) = expected_outout_name {
+        let workspace_path = Path::new(".")
+            .canonicalize()
+            .unwrap()
+            .join(cargo_project_name);
+        let expected_output_path = workspace_path.join(expected_outout_name);
+        let expected_output = fs::read_to_string(expected_output_path).unwrap();
+        Ok((
+            String::from_utf8(output.stdout).unwrap(),
+            Some(expected_output),
+        ))
+    } else {
+        Ok((String::from_utf8(output.stdout).unwrap(), None))
+    }
+}
+
+/// Install the plugin (only once) and run it with the `cargo` command on the cargo project
+/// (see `run_with_cargo_bin`), returning its output whether it succeeds or not.
+fn run_plugin(cargo_project_name: &str, plugin_args: &[&str]) -> Output {
     // Install the plugin
     let root_dir = env::temp_dir().join(PLUGIN_NAME);
     let current_dir = Path::new(".").canonicalize().unwrap();
@@ -60,19 +82,7 @@ pub fn run_with_cargo_bin(
     let _ = fs::remove_dir_all(workspace_path.join("target"));
 
     // Run the plugin
-    let output = cargo_cmd.output().unwrap();
-    assert!(output.status.success());
-
-    if let Some(expected_outout_name) = expected_outout_name {
-        let expected_output_path = workspace_path.join(expected_outout_name);
-        let expected_output = fs::read_to_string(expected_output_path).unwrap();
-        Ok((
-            String::from_utf8(output.stdout).unwrap(),
-            Some(expected_output),
-        ))
-    } else {
-        Ok((String::from_utf8(output.stdout).unwrap(), None))
-    }
+    cargo_cmd.output().unwrap()
 }
 
 pub fn create_cargo_project_with_snippet(snippet: &str) -> Result<(), String> {
@@ -115,3 +125,44 @@ pub fn run_with_cargo_bin_and_snippet(
     remove_cargo_project_with_snippet().unwrap();
     result
 }
+
+/// Run the plugin on the snippet (see `run_with_cargo_bin_and_snippet`), expecting it to fail.
+///
+/// # Returns
+/// The standard error of the plugin, e.g., with the errors of the compiler.
+#[allow(dead_code)] // FIXME: https://github.com/rust-lang/rust/issues/46379
+pub fn run_with_cargo_bin_and_snippet_failing(
+    snippet: &str,
+    plugin_args: &[&str],
+) -> Result<String, String> {
+    create_cargo_project_with_snippet(snippet).unwrap();
+    let output = run_plugin("tests/workspaces/temp", plugin_args);
+    remove_cargo_project_with_snippet().unwrap();
+    assert!(!output.status.success());
+    Ok(String::from_utf8(output.stderr).unwrap())
+}
+
+/// Remove the crate hashes from the printed paths, e.g., `DefId(0:5 ~ temp[6235]::main)`
+/// becomes `DefId(0:5 ~ temp::main)`, since they depend on the compiler.
+/// The other brackets, e.g., of `bb0[0]`, are kept.
+#[allow(dead_code)] // FIXME: https://github.com/rust-lang/rust/issues/46379
+pub fn strip_crate_hashes(output: &str) -> String {
+    let mut stripped = String::with_capacity(output.len());
+    let mut rest = output;
+    while let Some(start) = rest.find('[') {
+        let (before, after) = rest.split_at(start);
+        stripped.push_str(before);
+        let hash_len = after[1..]
+            .find(']')
+            .filter(|&len| len == 4 && after[1..=len].chars().all(|c| c.is_ascii_hexdigit()));
+        match hash_len {
+            Some(len) => rest = &after[len + 2..],
+            None => {
+                stripped.push('[');
+                rest = &after[1..];
+            }
+        }
+    }
+    stripped.push_str(rest);
+    stripped
+}
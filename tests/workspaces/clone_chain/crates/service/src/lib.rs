@@ -0,0 +1,13 @@
+use storage::Record;
+
+pub fn process(record: &mut Record) -> usize {
+    transform(record, 2)
+}
+
+fn transform(record: &mut Record, times: usize) -> usize {
+    record.data.push('!');
+    let copy = Record {
+        data: record.data.clone(),
+    };
+    storage::persist(copy) * times
+}
@@ -0,0 +1,17 @@
+use storage::Record;
+
+#[test]
+fn test_process() {
+    let mut record = Record {
+        data: String::from("record"),
+    };
+    assert_eq!(service::process(&mut record), 14);
+}
+
+#[test]
+fn test_persist() {
+    let record = Record {
+        data: String::from("record"),
+    };
+    assert_eq!(storage::persist(record), 6);
+}
@@ -0,0 +1,7 @@
+pub struct Record {
+    pub data: String,
+}
+
+pub fn persist(record: Record) -> usize {
+    record.data.len()
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let mut record = storage::Record {
+        data: String::from("record"),
+    };
+    service::process(&mut record);
+    storage::persist(record);
+}
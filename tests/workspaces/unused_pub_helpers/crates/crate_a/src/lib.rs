@@ -0,0 +1,17 @@
+pub fn used_by_main(n: u64) -> u64 {
+    n + 1
+}
+
+pub fn used_by_crate_b(n: u64) -> u64 {
+    n * 2
+}
+
+pub fn unused_helper(n: u64) -> u64 {
+    private_helper(n)
+}
+
+fn private_helper(n: u64) -> u64 {
+    n
+}
+
+fn private_dead() {}
@@ -0,0 +1,3 @@
+pub fn run(n: u64) -> u64 {
+    crate_a::used_by_crate_b(n)
+}
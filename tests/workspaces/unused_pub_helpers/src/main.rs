@@ -0,0 +1,4 @@
+fn main() {
+    let _ = crate_a::used_by_main(1);
+    let _ = crate_b::run(2);
+}